@@ -0,0 +1,2377 @@
+use std::collections::HashMap;
+use std::mem;
+
+use crate::checker::{
+    error::{CheckError, CheckErrorKind},
+    exhaustiveness::missing_patterns,
+    generics::{bind, overlap, resolve, unify, Bindings},
+    symbols::{
+        bound_pairs, BuiltinMethod, EnumSignature, ImplSignature, Signature, StructSignature, Symbol,
+        SymbolTable, TraitSignature,
+    },
+};
+use crate::diagnostics::span::Span;
+use crate::parser::{
+    tree::{
+        ParseTree, Item, Input, Stmt, Expr, VariableNode, BlockNode, FunctionNode,
+        FunctionCallNode, StatementNode, BinaryOpNode, UnaryOpNode, CastNode, Number, NumberNode, IfNode,
+        ElseBranch, LoopNode, LoopKind, AssignNode, StructNode, StructLiteralNode, FieldNode,
+        VariableCallNode, EnumNode, VariantCallNode, MatchNode, Pattern, ArrayNode, ArrayElements,
+        IndexNode, MethodCallNode, TupleNode, DestructureNode, BoundNode, TraitNode, ImplNode,
+        FunctionArgNode,
+    },
+    types::Type,
+    statements::Statement,
+    variables::VariableKind,
+    operators::AssignOperator,
+};
+
+// Semantic pass run between parsing and execution, it makes sure every
+// declared type is respected
+#[derive(Default)]
+pub struct Checker {
+    functions: HashMap<String, Signature>,
+    structs: HashMap<String, StructSignature>,
+    enums: HashMap<String, EnumSignature>,
+    traits: HashMap<String, TraitSignature>,
+    impls: Vec<ImplSignature>,
+    globals: SymbolTable,
+    // Globals declared by the last input of the REPL, which are only known once
+    // their value has been evaluated
+    pending_globals: Vec<(String, Symbol)>,
+    // Variables of the function (or REPL statement) being checked
+    locals: Option<SymbolTable>,
+    // None outside of any function
+    return_type: Option<Type>,
+    // Bounds of the type parameters of the function being checked
+    bounds: Vec<(String, String)>,
+    // Whether the block being checked is the branch of an 'if' expression
+    in_expression: bool,
+    // Labels of the loops enclosing the code being checked, the innermost being the last
+    loops: Vec<Option<String>>,
+    errors: Vec<CheckError>,
+}
+
+impl Checker {
+    pub fn new() -> Self {
+        Self {
+            functions: HashMap::new(),
+            structs: HashMap::new(),
+            enums: HashMap::new(),
+            traits: HashMap::new(),
+            impls: Vec::new(),
+            globals: SymbolTable::new(),
+            pending_globals: Vec::new(),
+            locals: None,
+            return_type: None,
+            bounds: Vec::new(),
+            in_expression: false,
+            loops: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn check(&mut self, tree: &mut ParseTree) -> Result<(), Vec<CheckError>> {
+        // Functions can be called, structs, enums and traits used, and
+        // traits implemented, before their declaration. Unlike in the REPL,
+        // a name cannot be declared twice, the first declaration is kept
+        for (i, item) in tree.nodes.iter().enumerate() {
+            if let Some(kind) = tree.nodes[..i].iter().find_map(|previous| redeclaration(previous, item)) {
+                self.error(kind, item.span());
+                continue;
+            }
+
+            match item {
+                Item::Function(node) => self.declare_function(node),
+                Item::Struct(node) => self.declare_struct(node),
+                Item::Enum(node) => self.declare_enum(node),
+                Item::Trait(node) => self.declare_trait(node),
+                Item::Impl(node) => self.declare_impl(node),
+                Item::Variable(_) => {}
+            }
+        }
+
+        self.check_main(&tree.nodes);
+
+        for item in &tree.nodes {
+            match item {
+                Item::Function(node) => self.check_bounds(&node.bounds),
+                Item::Struct(node) => self.check_struct(node),
+                Item::Enum(node) => self.check_enum(node),
+                Item::Trait(node) => self.check_trait(node),
+                Item::Impl(node) => self.check_impl(node),
+                Item::Variable(_) => {}
+            }
+        }
+
+        for item in &mut tree.nodes {
+            if let Item::Variable(node) = item {
+                self.check_global(node);
+            }
+        }
+
+        for item in &mut tree.nodes {
+            match item {
+                Item::Function(node) => self.check_function(node),
+                Item::Impl(node) => {
+                    for method in &mut node.methods {
+                        self.check_function(method);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        self.finish()
+    }
+
+    // Checks an input of the REPL, what it declares is only kept if it is valid.
+    // Global variables are declared by `declare_pending` once evaluated
+    pub fn check_input(&mut self, input: &mut Input) -> Result<(), Vec<CheckError>> {
+        self.pending_globals.clear();
+
+        match input {
+            Input::Item(Item::Function(node)) => {
+                let previous = self.functions.get(&node.name).cloned();
+
+                self.declare_function(node);
+                self.check_bounds(&node.bounds);
+                self.check_function(node);
+
+                if !self.errors.is_empty() {
+                    match previous {
+                        Some(signature) => self.functions.insert(node.name.clone(), signature),
+                        None => self.functions.remove(&node.name),
+                    };
+                }
+            }
+            Input::Item(Item::Struct(node)) => {
+                let previous = self.structs.get(&node.name).cloned();
+
+                self.declare_struct(node);
+                self.check_struct(node);
+
+                if !self.errors.is_empty() {
+                    match previous {
+                        Some(signature) => self.structs.insert(node.name.clone(), signature),
+                        None => self.structs.remove(&node.name),
+                    };
+                }
+            }
+            Input::Item(Item::Enum(node)) => {
+                let previous = self.enums.get(&node.name).cloned();
+
+                self.declare_enum(node);
+                self.check_enum(node);
+
+                if !self.errors.is_empty() {
+                    match previous {
+                        Some(signature) => self.enums.insert(node.name.clone(), signature),
+                        None => self.enums.remove(&node.name),
+                    };
+                }
+            }
+            Input::Item(Item::Trait(node)) => {
+                let previous = self.traits.get(&node.name).cloned();
+
+                self.declare_trait(node);
+                self.check_trait(node);
+
+                if !self.errors.is_empty() {
+                    match previous {
+                        Some(signature) => self.traits.insert(node.name.clone(), signature),
+                        None => self.traits.remove(&node.name),
+                    };
+                }
+            }
+            Input::Item(Item::Impl(node)) => {
+                let previous = self.impls.clone();
+
+                // A new impl of a trait for the same type replaces the previous one
+                self.impls.retain(|other| other.trait_name != node.trait_name || other.target != node.target);
+                self.declare_impl(node);
+                self.check_impl(node);
+                for method in &mut node.methods {
+                    self.check_function(method);
+                }
+
+                if !self.errors.is_empty() {
+                    self.impls = previous;
+                }
+            }
+            Input::Item(Item::Variable(node)) => {
+                self.check_global_value(node);
+
+                if self.errors.is_empty() {
+                    self.pending_globals.extend(Symbol::from_variable(node).map(|symbol| (node.name.clone(), symbol)));
+                }
+            }
+            // Its bindings are globals, they are first declared apart from them
+            Input::Stmt(Stmt::Destructure(node)) => {
+                self.locals = Some(SymbolTable::new());
+                self.check_destructure(node);
+                let bindings = self.locals.take().map(SymbolTable::into_outermost).unwrap_or_default();
+
+                if self.errors.is_empty() {
+                    self.pending_globals.extend(bindings);
+                }
+            }
+            Input::Stmt(stmt) => {
+                self.locals = Some(SymbolTable::new());
+                self.check_stmt(stmt);
+                self.locals = None;
+            }
+        }
+
+        self.finish()
+    }
+
+    // Called once the last input has run without error
+    pub fn declare_pending(&mut self) {
+        for (name, symbol) in mem::take(&mut self.pending_globals) {
+            self.globals.declare(&name, symbol);
+        }
+    }
+
+    fn finish(&mut self) -> Result<(), Vec<CheckError>> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(mem::take(&mut self.errors))
+        }
+    }
+
+    fn error(&mut self, kind: CheckErrorKind, span: Span) {
+        self.errors.push(CheckError::new(kind, span));
+    }
+
+    fn declare_function(&mut self, node: &FunctionNode) {
+        self.functions.insert(node.name.clone(), Signature {
+            type_params: node.type_params.clone(),
+            bounds: bound_pairs(&node.bounds),
+            args: node.args.iter().map(|arg| arg.var_type.clone()).collect(),
+            return_type: node.return_type.clone(),
+        });
+    }
+
+    // A program is run by calling its `main` function, which must not need
+    // any argument
+    fn check_main(&mut self, items: &[Item]) {
+        let main = items.iter().find_map(|item| match item {
+            Item::Function(node) if node.name == "main" => Some(node),
+            _ => None,
+        });
+
+        match main {
+            None => self.error(CheckErrorKind::MissingMain, Span::default()),
+            Some(node) if !node.args.is_empty() || !node.type_params.is_empty() =>
+                self.error(CheckErrorKind::MainSignature, node.span),
+            Some(_) => {}
+        }
+    }
+
+    fn declare_struct(&mut self, node: &StructNode) {
+        self.structs.insert(node.name.clone(), StructSignature::from_struct(node));
+    }
+
+    fn check_struct(&mut self, node: &StructNode) {
+        for (i, field) in node.fields.iter().enumerate() {
+            self.check_type(&field.field_type, field.span);
+
+            if node.fields[..i].iter().any(|other| other.name == field.name) {
+                self.error(CheckErrorKind::DuplicateField(field.name.clone()), field.span);
+            }
+        }
+    }
+
+    fn declare_enum(&mut self, node: &EnumNode) {
+        self.enums.insert(node.name.clone(), EnumSignature::from_enum(node));
+    }
+
+    fn check_enum(&mut self, node: &EnumNode) {
+        for (i, variant) in node.variants.iter().enumerate() {
+            for payload_type in &variant.payload {
+                self.check_type(payload_type, variant.span);
+            }
+
+            if node.variants[..i].iter().any(|other| other.name == variant.name) {
+                self.error(CheckErrorKind::DuplicateVariant(variant.name.clone()), variant.span);
+            }
+        }
+    }
+
+    fn declare_trait(&mut self, node: &TraitNode) {
+        self.traits.insert(node.name.clone(), TraitSignature::from_trait(node));
+    }
+
+    fn check_trait(&mut self, node: &TraitNode) {
+        for (i, method) in node.methods.iter().enumerate() {
+            if method.args.first().is_none_or(|arg| arg.name != "self") {
+                self.error(CheckErrorKind::MissingSelf(method.name.clone()), method.span);
+            }
+
+            self.check_args(&method.args);
+            self.check_type(&method.return_type, method.span);
+
+            if node.methods[..i].iter().any(|other| other.name == method.name) {
+                self.error(CheckErrorKind::DuplicateMethod(method.name.clone()), method.span);
+            }
+        }
+    }
+
+    // Impls of a trait for overlapping types conflict, the first one is kept
+    fn declare_impl(&mut self, node: &ImplNode) {
+        let signature = ImplSignature::from_impl(node);
+
+        let conflicting = self.impls.iter().any(|other| {
+            other.trait_name == signature.trait_name && overlap(&other.target, &signature.target)
+        });
+
+        if conflicting {
+            self.error(CheckErrorKind::ConflictingImpl {
+                trait_name: signature.trait_name,
+                found: signature.target,
+            }, node.span);
+            return;
+        }
+
+        self.impls.push(signature);
+    }
+
+    // The methods of an impl must be those of its trait, with `Self` being the
+    // implementing type. Their bodies are checked like functions
+    fn check_impl(&mut self, node: &ImplNode) {
+        self.check_type(&node.target, node.span);
+        self.check_bounds(&node.bounds);
+
+        for param in &node.type_params {
+            if !node.target.params().contains(&param.as_str()) {
+                self.error(CheckErrorKind::UnusedTypeParam(param.clone()), node.span);
+            }
+        }
+
+        let signature = match self.traits.get(&node.trait_name) {
+            Some(signature) => signature.clone(),
+            None => {
+                self.error(CheckErrorKind::UndefinedTrait(node.trait_name.clone()), node.span);
+                return;
+            }
+        };
+
+        let self_type = Bindings::from([("Self".to_string(), node.target.clone())]);
+
+        for (i, method) in node.methods.iter().enumerate() {
+            if node.methods[..i].iter().any(|other| other.name == method.name) {
+                self.error(CheckErrorKind::DuplicateMethod(method.name.clone()), method.span);
+                continue;
+            }
+
+            let declared = match signature.method(&method.name) {
+                Some(declared) => declared,
+                None => {
+                    self.error(CheckErrorKind::ExtraMethod {
+                        trait_name: node.trait_name.clone(),
+                        method: method.name.clone(),
+                    }, method.span);
+                    continue;
+                }
+            };
+
+            let args = declared.args.iter().map(|arg| arg.substitute(&self_type)).collect::<Vec<_>>();
+            let return_type = declared.return_type.substitute(&self_type);
+
+            match method.args.split_first() {
+                Some((receiver, found)) if receiver.name == "self" => {
+                    if !found.iter().map(|arg| &arg.var_type).eq(&args) || method.return_type != return_type {
+                        let args = args.iter().map(|arg| format!(", {}", arg)).collect::<String>();
+                        self.error(CheckErrorKind::MethodSignature {
+                            trait_name: node.trait_name.clone(),
+                            method: method.name.clone(),
+                            expected: format!("fcn {}(self{}) -> {}", method.name, args, return_type),
+                        }, method.span);
+                    }
+                }
+                _ => self.error(CheckErrorKind::MissingSelf(method.name.clone()), method.span),
+            }
+        }
+
+        for (method, _) in &signature.methods {
+            if !node.methods.iter().any(|implemented| implemented.name == *method) {
+                self.error(CheckErrorKind::MissingMethod {
+                    trait_name: node.trait_name.clone(),
+                    method: method.clone(),
+                }, node.span);
+            }
+        }
+    }
+
+    fn check_bounds(&mut self, bounds: &[BoundNode]) {
+        for bound in bounds {
+            if !self.traits.contains_key(&bound.trait_name) {
+                self.error(CheckErrorKind::UndefinedTrait(bound.trait_name.clone()), bound.span);
+            }
+        }
+    }
+
+    // Whether a type implements a trait, a type parameter does if it is
+    // bounded by it. The bounds of a generic impl must hold for the types
+    // its parameters stand for
+    fn implements(&self, checked: &Type, trait_name: &str) -> bool {
+        if let Type::Param(param) = checked {
+            return self.bounds.iter().any(|(bounded, bound)| bounded == param && bound == trait_name);
+        }
+
+        self.impls.iter()
+            .filter(|signature| signature.trait_name == trait_name)
+            .any(|signature| {
+                let mut bindings = Bindings::new();
+                unify(&signature.target, checked, &mut bindings)
+                    && signature.bounds.iter().all(|(param, bound)| {
+                        bindings.get(param).is_some_and(|arg| self.implements(arg, bound))
+                    })
+            })
+    }
+
+    // Reports the type arguments not implementing the traits bounding their parameter
+    fn check_impls(&mut self, bounds: &[(String, String)], bindings: &Bindings, span: Span) {
+        for (param, trait_name) in bounds {
+            if let Some(found) = bindings.get(param) {
+                if !self.implements(found, trait_name) {
+                    self.error(CheckErrorKind::MissingImpl {
+                        trait_name: trait_name.clone(),
+                        found: found.clone(),
+                    }, span);
+                }
+            }
+        }
+    }
+
+    // Makes sure the struct or enum a type refers to is declared
+    fn check_type(&mut self, checked: &Type, span: Span) {
+        match checked {
+            Type::Named(name, args) => {
+                let params = self.structs.get(name).map(|signature| signature.type_params.len())
+                    .or_else(|| self.enums.get(name).map(|signature| signature.type_params.len()));
+
+                match params {
+                    None => self.error(CheckErrorKind::UndefinedType(name.clone()), span),
+                    Some(expected) if expected != args.len() => self.error(CheckErrorKind::TypeArgCount {
+                        name: name.clone(),
+                        expected,
+                        found: args.len(),
+                    }, span),
+                    Some(_) => {}
+                }
+
+                for arg in args {
+                    self.check_type(arg, span);
+                }
+            }
+            Type::Array(element_type, _) | Type::List(element_type) => self.check_type(element_type, span),
+            Type::Tuple(types) => {
+                for element_type in types {
+                    self.check_type(element_type, span);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn declare_global(&mut self, node: &VariableNode) {
+        if let Some(symbol) = Symbol::from_variable(node) {
+            self.globals.declare(&node.name, symbol);
+        }
+    }
+
+    fn check_global(&mut self, node: &mut VariableNode) {
+        self.check_global_value(node);
+        self.declare_global(node);
+    }
+
+    fn check_global_value(&mut self, node: &mut VariableNode) {
+        self.check_value(node);
+
+        if node.kind == VariableKind::Const {
+            if let Some(span) = self.non_constant(&node.value) {
+                self.error(CheckErrorKind::NonConstant, span);
+            }
+        }
+    }
+
+    // Checks the value of a declaration, the type of the variable is inferred
+    // from it when the declaration does not give one
+    fn check_value(&mut self, node: &mut VariableNode) {
+        if let Some(var_type) = &node.var_type {
+            self.check_type(var_type, node.span);
+        }
+
+        match &node.var_type {
+            Some(var_type) => self.check_typed(&mut node.value, var_type),
+            None => node.var_type = self.check_expr(&mut node.value, None),
+        }
+    }
+
+    // Span of the first part of the expression which cannot be evaluated
+    // before running the program, if any
+    fn non_constant(&self, expr: &Expr) -> Option<Span> {
+        match expr {
+            Expr::Bool(_) | Expr::Number(_) | Expr::String(_) | Expr::Char(_) => None,
+            Expr::VariableCall(node) => match self.lookup(&node.name) {
+                Some(symbol) if symbol.kind != VariableKind::Const => Some(node.span),
+                // Undeclared variables are already reported
+                _ => None,
+            },
+            Expr::UnaryOp(node) => self.non_constant(&node.operand),
+            Expr::Cast(node) => self.non_constant(&node.expr),
+            Expr::Field(node) => self.non_constant(&node.expr),
+            Expr::StructLiteral(node) => node.fields.iter()
+                .find_map(|field| self.non_constant(&field.value)),
+            Expr::VariantCall(node) => node.args.iter().find_map(|arg| self.non_constant(arg)),
+            Expr::Match(node) => Some(node.span),
+            Expr::Array(node) => match &node.elements {
+                ArrayElements::Values(values) => values.iter().find_map(|value| self.non_constant(value)),
+                ArrayElements::Repeat { value, .. } => self.non_constant(value),
+            },
+            Expr::Index(node) => self.non_constant(&node.expr)
+                .or_else(|| self.non_constant(&node.index)),
+            Expr::MethodCall(node) => Some(node.span),
+            Expr::Tuple(node) => node.elements.iter().find_map(|element| self.non_constant(element)),
+            Expr::BinaryOp(node) => self.non_constant(&node.left)
+                .or_else(|| self.non_constant(&node.right)),
+            Expr::FunctionCall(node) => Some(node.span),
+            Expr::If(node) => Some(node.span),
+        }
+    }
+
+    fn symbols_mut(&mut self) -> &mut SymbolTable {
+        match &mut self.locals {
+            Some(locals) => locals,
+            None => &mut self.globals,
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<&Symbol> {
+        self.locals.as_ref()
+            .and_then(|locals| locals.get(name))
+            .or_else(|| self.globals.get(name))
+    }
+
+    fn check_args(&mut self, args: &[FunctionArgNode]) {
+        for (i, arg) in args.iter().enumerate() {
+            self.check_type(&arg.var_type, arg.span);
+
+            if args[..i].iter().any(|other| other.name == arg.name) {
+                self.error(CheckErrorKind::DuplicateArgument(arg.name.clone()), arg.span);
+            }
+        }
+    }
+
+    fn check_function(&mut self, node: &mut FunctionNode) {
+        self.bounds = bound_pairs(&node.bounds);
+
+        self.check_args(&node.args);
+        self.check_type(&node.return_type, node.span);
+
+        let mut locals = SymbolTable::new();
+        for arg in &node.args {
+            locals.declare(&arg.name, Symbol { var_type: arg.var_type.clone(), kind: VariableKind::Let });
+        }
+
+        self.locals = Some(locals);
+        self.return_type = Some(node.return_type.clone());
+
+        self.check_block(&mut node.block);
+
+        if !block_returns(&node.block) {
+            self.error(CheckErrorKind::MissingReturn(node.name.clone()), node.span);
+        }
+
+        self.locals = None;
+        self.return_type = None;
+        self.bounds.clear();
+    }
+
+    fn check_block(&mut self, node: &mut BlockNode) {
+        self.symbols_mut().push_scope();
+
+        for stmt in &mut node.nodes {
+            self.check_stmt(stmt);
+        }
+
+        self.symbols_mut().pop_scope();
+    }
+
+    fn check_stmt(&mut self, stmt: &mut Stmt) {
+        match stmt {
+            Stmt::Variable(node) => {
+                if node.kind == VariableKind::Const {
+                    self.error(CheckErrorKind::LocalConst, node.span);
+                }
+
+                self.check_value(node);
+
+                if let Some(symbol) = Symbol::from_variable(node) {
+                    self.symbols_mut().declare(&node.name, symbol);
+                }
+            }
+            Stmt::Destructure(node) => self.check_destructure(node),
+            Stmt::Block(node) => self.check_block(node),
+            Stmt::If(node) => self.check_if(node),
+            Stmt::Loop(node) => self.check_loop(node),
+            Stmt::Match(node) => {
+                self.check_match(node, false, None);
+            }
+            Stmt::Assign(node) => self.check_assign(node),
+            Stmt::Statement(node) => self.check_statement(node),
+            Stmt::Expr(expr) => {
+                self.check_expr(expr, None);
+            }
+        }
+    }
+
+    // The pattern must match every value of the type, its bindings are
+    // declared like variables of the same kind
+    fn check_destructure(&mut self, node: &mut DestructureNode) {
+        if node.kind == VariableKind::Const {
+            self.error(CheckErrorKind::LocalConst, node.span);
+        }
+
+        let value_type = match &node.var_type {
+            Some(var_type) => {
+                self.check_type(var_type, node.span);
+                self.check_typed(&mut node.value, var_type);
+                var_type.clone()
+            }
+            None => match self.check_expr(&mut node.value, None) {
+                Some(value_type) => value_type,
+                None => return,
+            },
+        };
+
+        let errors = self.errors.len();
+        self.check_pattern(&mut node.pattern, &value_type, node.kind);
+
+        if self.errors.len() == errors {
+            let missing = missing_patterns(&self.enums, &[&node.pattern], &value_type);
+            if !missing.is_empty() {
+                self.error(CheckErrorKind::RefutablePattern(missing), node.pattern.span());
+            }
+        }
+    }
+
+    fn check_if(&mut self, node: &mut IfNode) {
+        self.check_typed(&mut node.condition, &Type::Bool);
+        self.check_block(&mut node.then_block);
+
+        match &mut node.else_branch {
+            Some(ElseBranch::If(else_if)) => self.check_if(else_if),
+            Some(ElseBranch::Block(block)) => self.check_block(block),
+            None => {}
+        }
+    }
+
+    // Both branches of an 'if' expression must have a value of the same type
+    fn check_if_expr(&mut self, node: &mut IfNode, expected: Option<&Type>) -> Option<Type> {
+        self.check_typed(&mut node.condition, &Type::Bool);
+        let then_type = self.check_block_value(&mut node.then_block, expected);
+
+        let expected = then_type.as_ref().or(expected);
+        let else_type = match &mut node.else_branch {
+            Some(ElseBranch::If(else_if)) => self.check_if_expr(else_if, expected),
+            Some(ElseBranch::Block(block)) => self.check_block_value(block, expected),
+            None => {
+                self.error(CheckErrorKind::MissingElse, node.span);
+                return None;
+            }
+        };
+        let (then_type, else_type) = (then_type?, else_type?);
+
+        if then_type != else_type {
+            let span = node.else_branch.as_ref().map_or(node.span, ElseBranch::span);
+            self.error(CheckErrorKind::Mismatch { expected: then_type, found: else_type }, span);
+            return None;
+        }
+
+        Some(then_type)
+    }
+
+    // Returns the type of the expression ending the block
+    fn check_block_value(&mut self, node: &mut BlockNode, expected: Option<&Type>) -> Option<Type> {
+        let in_expression = mem::replace(&mut self.in_expression, true);
+        let loops = mem::take(&mut self.loops);
+        self.symbols_mut().push_scope();
+
+        let value_type = match node.nodes.split_last_mut() {
+            Some((tail, stmts)) if tail.is_tail_value() => {
+                for stmt in stmts {
+                    self.check_stmt(stmt);
+                }
+
+                match tail {
+                    Stmt::If(tail) => self.check_if_expr(tail, expected),
+                    Stmt::Match(tail) => self.check_match(tail, true, expected),
+                    Stmt::Expr(tail) => self.check_expr(tail, expected),
+                    _ => None,
+                }
+            }
+            _ => {
+                for stmt in &mut node.nodes {
+                    self.check_stmt(stmt);
+                }
+                self.error(CheckErrorKind::MissingValue, node.span);
+                None
+            }
+        };
+
+        self.symbols_mut().pop_scope();
+        self.in_expression = in_expression;
+        self.loops = loops;
+        value_type
+    }
+
+    fn check_loop(&mut self, node: &mut LoopNode) {
+        // The scope of the loop variable
+        self.symbols_mut().push_scope();
+
+        match &mut node.kind {
+            LoopKind::Infinite => {}
+            LoopKind::While(condition) => self.check_typed(condition, &Type::Bool),
+            LoopKind::For { var_name, start, end } => {
+                let (start_type, end_type) = self.check_operands(start, end, None);
+
+                if let (Some(start_type), Some(end_type)) = (start_type, end_type) {
+                    if start_type != end_type {
+                        self.error(CheckErrorKind::Mismatch {
+                            expected: start_type.clone(),
+                            found: end_type,
+                        }, end.span());
+                    } else if !start_type.is_integer() {
+                        self.error(CheckErrorKind::InvalidRange(start_type.clone()), start.span());
+                    }
+
+                    self.symbols_mut().declare(var_name, Symbol {
+                        var_type: start_type,
+                        kind: VariableKind::Let,
+                    });
+                }
+            }
+        }
+
+        // A loop inside an 'if' expression can be left
+        let in_expression = mem::replace(&mut self.in_expression, false);
+        self.loops.push(node.label.clone());
+
+        self.check_block(&mut node.block);
+
+        self.loops.pop();
+        self.in_expression = in_expression;
+        self.symbols_mut().pop_scope();
+    }
+
+    fn check_assign(&mut self, node: &mut AssignNode) {
+        let target_type = match assigned_variable(&node.target) {
+            Some(target) => {
+                if let Some(symbol) = self.lookup(&target.name) {
+                    if !symbol.kind.is_mutable() {
+                        self.error(CheckErrorKind::AssignToImmutable(target.name.clone()), target.span);
+                    }
+                }
+                self.check_expr(&mut node.target, None)
+            }
+            None => {
+                self.error(CheckErrorKind::InvalidAssignTarget, node.target.span());
+                None
+            }
+        };
+
+        let target_type = match target_type {
+            Some(t) => t,
+            None => {
+                self.check_expr(&mut node.value, None);
+                return;
+            }
+        };
+
+        let operator = match node.operator {
+            AssignOperator::Assign => {
+                self.check_typed(&mut node.value, &target_type);
+                return;
+            }
+            AssignOperator::Compound(operator) => operator,
+        };
+
+        // The amount of a shift can be of another integer type
+        let value_type = if operator.is_shift() {
+            self.check_expr(&mut node.value, None)
+        } else {
+            self.check_typed(&mut node.value, &target_type);
+            Some(target_type.clone())
+        };
+
+        if !operator.accepts(&target_type) {
+            self.error(CheckErrorKind::InvalidOperand {
+                operator: node.operator.to_string(),
+                found: target_type,
+            }, node.target.span());
+        } else if let Some(found) = value_type.filter(|t| !operator.accepts(t)) {
+            self.error(CheckErrorKind::InvalidOperand {
+                operator: node.operator.to_string(),
+                found,
+            }, node.value.span());
+        }
+    }
+
+    fn check_statement(&mut self, node: &mut StatementNode) {
+        // Leaving an expression halfway is not supported
+        if self.in_expression {
+            self.error(CheckErrorKind::StatementInExpression(node.stat_type.to_string()), node.span);
+            return;
+        }
+
+        if node.stat_type != Statement::Return {
+            if self.loops.is_empty() {
+                self.error(CheckErrorKind::OutsideLoop(node.stat_type.to_string()), node.span);
+            } else if let Some(label) = &node.label {
+                if !self.loops.contains(&Some(label.clone())) {
+                    self.error(CheckErrorKind::UndefinedLabel(label.clone()), node.span);
+                }
+            }
+            return;
+        }
+
+        match (self.return_type.clone(), &mut node.value) {
+            (None, _) => self.error(CheckErrorKind::ReturnOutsideFunction, node.span),
+            (Some(return_type), Some(value)) => self.check_typed(value, &return_type),
+            (Some(_), None) => {}
+        }
+    }
+
+    // Checks an expression which must be of type `expected`
+    fn check_typed(&mut self, expr: &mut Expr, expected: &Type) {
+        if let Some(found) = self.check_expr(expr, Some(expected)) {
+            if found != *expected {
+                self.error(CheckErrorKind::Mismatch {
+                    expected: expected.clone(),
+                    found,
+                }, expr.span());
+            }
+        }
+    }
+
+    // Returns the type of an expression, or None if it is invalid (in which
+    // case the error has already been reported). `expected` is the type the
+    // context expects, it gives its type to number literals
+    fn check_expr(&mut self, expr: &mut Expr, expected: Option<&Type>) -> Option<Type> {
+        match expr {
+            Expr::Bool(_) => Some(Type::Bool),
+            Expr::Number(node) => self.check_number(node, expected),
+            Expr::String(_) => Some(Type::String),
+            Expr::Char(_) => Some(Type::Char),
+            Expr::VariableCall(node) => match self.lookup(&node.name) {
+                Some(symbol) => Some(symbol.var_type.clone()),
+                None => {
+                    self.error(CheckErrorKind::UndefinedVariable(node.name.clone()), node.span);
+                    None
+                }
+            },
+            Expr::FunctionCall(node) => self.check_call(node, expected),
+            Expr::BinaryOp(node) => self.check_binary_op(node, expected),
+            Expr::UnaryOp(node) => self.check_unary_op(node, expected),
+            Expr::If(node) => self.check_if_expr(node, expected),
+            Expr::Cast(node) => self.check_cast(node),
+            Expr::StructLiteral(node) => self.check_struct_literal(node, expected),
+            Expr::Field(node) => self.check_field(node),
+            Expr::VariantCall(node) => self.check_variant_call(node, expected),
+            Expr::Match(node) => self.check_match(node, true, expected),
+            Expr::Array(node) => self.check_array(node, expected),
+            Expr::Index(node) => self.check_index(node),
+            Expr::MethodCall(node) => self.check_method_call(node),
+            Expr::Tuple(node) => self.check_tuple(node, expected),
+        }
+    }
+
+    fn check_number(&mut self, node: &mut NumberNode, expected: Option<&Type>) -> Option<Type> {
+        let number_type = match (expected, node.value) {
+            (Some(t), _) if t.is_numeric() => t.clone(),
+            (_, Number::Integer { .. }) => Type::Int64,
+            (_, Number::Float(_)) => Type::Float64,
+        };
+
+        let fits = match node.value {
+            Number::Integer { negative, magnitude } if number_type.is_integer() =>
+                number_type.contains(negative, magnitude),
+            // Any integer can be converted to a float
+            Number::Integer { .. } => true,
+            Number::Float(_) if number_type.is_integer() => {
+                self.error(CheckErrorKind::Mismatch {
+                    expected: number_type,
+                    found: Type::Float64,
+                }, node.span);
+                return None;
+            }
+            Number::Float(value) if number_type == Type::Float32 => (value as f32).is_finite(),
+            Number::Float(value) => value.is_finite(),
+        };
+
+        if !fits {
+            self.error(CheckErrorKind::LiteralOutOfRange(number_type), node.span);
+            return None;
+        }
+
+        node.number_type = Some(number_type.clone());
+        Some(number_type)
+    }
+
+    fn check_call(&mut self, node: &mut FunctionCallNode, expected: Option<&Type>) -> Option<Type> {
+        let signature = match self.functions.get(&node.name) {
+            Some(signature) => signature.clone(),
+            None => {
+                self.error(CheckErrorKind::UndefinedFunction(node.name.clone()), node.span);
+                return None;
+            }
+        };
+
+        if node.args.len() != signature.args.len() {
+            self.error(CheckErrorKind::ArgumentCount {
+                name: node.name.clone(),
+                expected: signature.args.len(),
+                found: node.args.len(),
+            }, node.span);
+        }
+
+        let values = node.args.iter_mut().zip(&signature.args).collect();
+        node.type_args = self.check_generic_args(&signature.type_params, values, &signature.return_type,
+                                                 expected, node.span)?;
+
+        let bindings = bind(&signature.type_params, &node.type_args);
+        self.check_impls(&signature.bounds, &bindings, node.span);
+
+        Some(signature.return_type.substitute(&bindings))
+    }
+
+    // Checks the values given to a declaration whose types can refer to its
+    // type parameters, and infers the types of these, which are returned. The
+    // type `expected` for `result` is used first, if it fits, then the values
+    // whose type does not depend on the context, then the others
+    fn check_generic_args(&mut self, type_params: &[String], mut values: Vec<(&mut Expr, &Type)>,
+                          result: &Type, expected: Option<&Type>, span: Span) -> Option<Vec<Type>> {
+        let mut bindings = Bindings::new();
+
+        if let Some(expected) = expected {
+            if !unify(result, expected, &mut bindings) {
+                bindings.clear();
+            }
+        }
+
+        values.sort_by_key(|(value, _)| value.is_untyped());
+        for (value, value_type) in values {
+            self.check_generic_arg(value, value_type, &mut bindings);
+        }
+
+        let mut type_args = Vec::new();
+        for param in type_params {
+            match bindings.get(param) {
+                Some(arg) => type_args.push(arg.clone()),
+                None => {
+                    self.error(CheckErrorKind::CannotInfer(param.clone()), span);
+                    return None;
+                }
+            }
+        }
+        Some(type_args)
+    }
+
+    fn check_generic_arg(&mut self, value: &mut Expr, value_type: &Type, bindings: &mut Bindings) {
+        match resolve(value_type, bindings) {
+            Some(resolved) => self.check_typed(value, &resolved),
+            None => {
+                if let Some(found) = self.check_expr(value, None) {
+                    if !unify(value_type, &found, bindings) {
+                        self.error(CheckErrorKind::Mismatch {
+                            expected: value_type.substitute(bindings),
+                            found,
+                        }, value.span());
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_binary_op(&mut self, node: &mut BinaryOpNode, expected: Option<&Type>) -> Option<Type> {
+        if node.operator.is_logical() {
+            self.check_typed(&mut node.left, &Type::Bool);
+            self.check_typed(&mut node.right, &Type::Bool);
+            return Some(Type::Bool);
+        }
+
+        // The amount of a shift does not depend on the shifted value, a
+        // literal one keeps its default type
+        if node.operator.is_shift() {
+            let left = self.check_expr(&mut node.left, expected);
+            let right = self.check_expr(&mut node.right, None);
+            let (left, right) = (left?, right?);
+
+            for (operand, operand_type) in [(&node.left, &left), (&node.right, &right)] {
+                if !operand_type.is_integer() {
+                    self.error(CheckErrorKind::InvalidOperand {
+                        operator: node.operator.to_string(),
+                        found: operand_type.clone(),
+                    }, operand.span());
+                    return None;
+                }
+            }
+            return Some(left);
+        }
+
+        // The result of a comparison says nothing about the type of its operands
+        let expected = if node.operator.is_comparison() { None } else { expected };
+
+        let (left, right) = self.check_operands(&mut node.left, &mut node.right, expected);
+        let (left, right) = (left?, right?);
+
+        if left != right {
+            self.error(CheckErrorKind::MismatchedOperands {
+                operator: node.operator.to_string(),
+                left,
+                right,
+            }, node.span);
+            return None;
+        }
+
+        if !node.operator.accepts(&left) {
+            self.error(CheckErrorKind::InvalidOperand {
+                operator: node.operator.to_string(),
+                found: left,
+            }, node.left.span());
+            return None;
+        }
+
+        if node.operator.is_comparison() {
+            Some(Type::Bool)
+        } else {
+            Some(left)
+        }
+    }
+
+    // Checks two expressions expected to be of the same type, a literal
+    // operand takes the type of the other one
+    fn check_operands(&mut self, left: &mut Expr, right: &mut Expr, expected: Option<&Type>)
+                      -> (Option<Type>, Option<Type>) {
+        if left.is_untyped() && !right.is_untyped() {
+            let right_type = self.check_expr(right, expected);
+            let left_type = self.check_expr(left, right_type.as_ref());
+            (left_type, right_type)
+        } else {
+            let left_type = self.check_expr(left, expected);
+            let right_type = self.check_expr(right, left_type.as_ref());
+            (left_type, right_type)
+        }
+    }
+
+    fn check_unary_op(&mut self, node: &mut UnaryOpNode, expected: Option<&Type>) -> Option<Type> {
+        let operand = self.check_expr(&mut node.operand, expected)?;
+
+        if !node.operator.accepts(&operand) {
+            self.error(CheckErrorKind::InvalidOperand {
+                operator: node.operator.to_string(),
+                found: operand,
+            }, node.operand.span());
+            return None;
+        }
+
+        Some(operand)
+    }
+
+    // The operand of a cast gets no expected type, a literal keeps its default one
+    fn check_cast(&mut self, node: &mut CastNode) -> Option<Type> {
+        self.check_type(&node.target, node.span);
+        let from = self.check_expr(&mut node.expr, None)?;
+
+        if !from.can_cast_to(&node.target) {
+            self.error(CheckErrorKind::InvalidCast {
+                from,
+                to: node.target.clone(),
+            }, node.expr.span());
+            return None;
+        }
+
+        Some(node.target.clone())
+    }
+
+    // Every field of the struct must be given once
+    fn check_struct_literal(&mut self, node: &mut StructLiteralNode, expected: Option<&Type>) -> Option<Type> {
+        for i in 0..node.fields.len() {
+            let field = &node.fields[i];
+            if node.fields[..i].iter().any(|other| other.name == field.name) {
+                self.error(CheckErrorKind::DuplicateField(field.name.clone()), field.span);
+            }
+        }
+
+        let signature = match self.structs.get(&node.name).cloned() {
+            Some(signature) => signature,
+            None => {
+                self.error(CheckErrorKind::UndefinedType(node.name.clone()), node.span);
+                for field in &mut node.fields {
+                    self.check_expr(&mut field.value, None);
+                }
+                return None;
+            }
+        };
+
+        for (field, _) in &signature.fields {
+            if !node.fields.iter().any(|given| given.name == *field) {
+                self.error(CheckErrorKind::MissingField {
+                    name: node.name.clone(),
+                    field: field.clone(),
+                }, node.span);
+            }
+        }
+
+        let mut values = Vec::new();
+        for field in &mut node.fields {
+            match signature.field(&field.name) {
+                Some(field_type) => values.push((&mut field.value, field_type)),
+                None => {
+                    self.error(CheckErrorKind::UndefinedField {
+                        found: Type::Named(node.name.clone(), Vec::new()),
+                        field: field.name.clone(),
+                    }, field.span);
+                    self.check_expr(&mut field.value, None);
+                }
+            }
+        }
+
+        let generic_type = Type::Named(node.name.clone(), signature.type_params.iter().cloned().map(Type::Param).collect());
+        node.type_args = self.check_generic_args(&signature.type_params, values, &generic_type, expected, node.span)?;
+
+        Some(Type::Named(node.name.clone(), node.type_args.clone()))
+    }
+
+    fn check_field(&mut self, node: &mut FieldNode) -> Option<Type> {
+        let found = self.check_expr(&mut node.expr, None)?;
+
+        let field_type = match &found {
+            Type::Named(name, args) => self.structs.get(name).and_then(|signature| {
+                let field_type = signature.field(&node.field)?;
+                Some(field_type.substitute(&bind(&signature.type_params, args)))
+            }),
+            // The fields of a tuple are named by their position
+            Type::Tuple(types) => node.field.parse::<usize>().ok()
+                .and_then(|position| types.get(position))
+                .cloned(),
+            _ => None,
+        };
+
+        if field_type.is_none() {
+            self.error(CheckErrorKind::UndefinedField { found, field: node.field.clone() }, node.span);
+        }
+
+        field_type
+    }
+
+    // The type of the elements is given by the context, or else by the first
+    // element which does not take its type from it. An array literal is a
+    // list where the context expects one
+    fn check_array(&mut self, node: &mut ArrayNode, expected: Option<&Type>) -> Option<Type> {
+        let (mut values, length) = match &mut node.elements {
+            ArrayElements::Values(values) => {
+                let length = values.len();
+                (values.iter_mut().collect::<Vec<_>>(), length)
+            }
+            ArrayElements::Repeat { value, length } => (vec![value.as_mut()], *length),
+        };
+
+        let element_type = match expected.and_then(Type::element_type) {
+            Some(element_type) => {
+                for value in values {
+                    self.check_typed(value, element_type);
+                }
+                element_type.clone()
+            }
+            None => {
+                if values.is_empty() {
+                    self.error(CheckErrorKind::UnknownElementType, node.span);
+                    return None;
+                }
+
+                let first = values.iter().position(|value| !value.is_untyped()).unwrap_or(0);
+                let element_type = self.check_expr(values[first], None)?;
+                for (i, value) in values.iter_mut().enumerate() {
+                    if i != first {
+                        self.check_typed(value, &element_type);
+                    }
+                }
+                element_type
+            }
+        };
+
+        let array_type = match expected {
+            Some(Type::List(_)) => Type::List(Box::new(element_type)),
+            _ => Type::Array(Box::new(element_type), length),
+        };
+        node.array_type = Some(array_type.clone());
+        Some(array_type)
+    }
+
+    // Each element gets its type from the matching one of the expected tuple, if any
+    fn check_tuple(&mut self, node: &mut TupleNode, expected: Option<&Type>) -> Option<Type> {
+        let hints = match expected {
+            Some(Type::Tuple(types)) if types.len() == node.elements.len() => Some(types),
+            _ => None,
+        };
+
+        let types = node.elements.iter_mut().enumerate()
+            .map(|(i, element)| self.check_expr(element, hints.map(|types| &types[i])))
+            .collect::<Vec<_>>();
+
+        types.into_iter().collect::<Option<Vec<_>>>().map(Type::Tuple)
+    }
+
+    fn check_index(&mut self, node: &mut IndexNode) -> Option<Type> {
+        let found = self.check_expr(&mut node.expr, None);
+
+        if let Some(index_type) = self.check_expr(&mut node.index, None) {
+            if !index_type.is_integer() {
+                self.error(CheckErrorKind::InvalidIndex(index_type), node.index.span());
+            }
+        }
+
+        let found = found?;
+        match found.element_type() {
+            Some(element_type) => Some(element_type.clone()),
+            None => {
+                self.error(CheckErrorKind::NotIndexable(found), node.expr.span());
+                None
+            }
+        }
+    }
+
+    // Methods which modify their receiver need it to be mutable, like an assignment
+    fn check_method_call(&mut self, node: &mut MethodCallNode) -> Option<Type> {
+        let receiver = self.check_expr(&mut node.receiver, None);
+
+        let signature = match receiver.and_then(|found| self.method_signature(node, found)) {
+            Some(signature) => signature,
+            None => {
+                for arg in &mut node.args {
+                    self.check_expr(arg, None);
+                }
+                return None;
+            }
+        };
+
+        if node.args.len() != signature.args.len() {
+            self.error(CheckErrorKind::ArgumentCount {
+                name: node.method.clone(),
+                expected: signature.args.len(),
+                found: node.args.len(),
+            }, node.span);
+        }
+
+        for (arg, arg_type) in node.args.iter_mut().zip(&signature.args) {
+            self.check_typed(arg, arg_type);
+        }
+
+        Some(signature.return_type)
+    }
+
+    // Signature of a method called on a value of type `found`: a built-in
+    // method, or else the method of the only trait declaring it which the
+    // type implements
+    fn method_signature(&mut self, node: &mut MethodCallNode, found: Type) -> Option<Signature> {
+        if let Some(method) = BuiltinMethod::lookup(&found, &node.method) {
+            if method.mutating {
+                match assigned_variable(&node.receiver) {
+                    Some(target) => {
+                        if self.lookup(&target.name).is_some_and(|symbol| !symbol.kind.is_mutable()) {
+                            self.error(CheckErrorKind::AssignToImmutable(target.name.clone()), target.span);
+                        }
+                    }
+                    None => self.error(CheckErrorKind::InvalidAssignTarget, node.receiver.span()),
+                }
+            }
+            return Some(method.signature);
+        }
+
+        let mut declaring = self.traits.iter()
+            .filter(|(_, signature)| signature.method(&node.method).is_some())
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        declaring.sort();
+
+        let implemented = declaring.iter()
+            .filter(|trait_name| self.implements(&found, trait_name))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let trait_name = match (implemented.as_slice(), declaring.as_slice()) {
+            ([trait_name], _) => trait_name.clone(),
+            ([], [trait_name]) => {
+                self.error(CheckErrorKind::MissingImpl { trait_name: trait_name.clone(), found }, node.span);
+                return None;
+            }
+            ([], _) => {
+                self.error(CheckErrorKind::UndefinedMethod { found, method: node.method.clone() }, node.span);
+                return None;
+            }
+            _ => {
+                self.error(CheckErrorKind::AmbiguousMethod {
+                    method: node.method.clone(),
+                    traits: implemented,
+                }, node.span);
+                return None;
+            }
+        };
+
+        let declared = self.traits[&trait_name].method(&node.method)?;
+        let self_type = Bindings::from([("Self".to_string(), found)]);
+        let signature = Signature {
+            type_params: Vec::new(),
+            bounds: Vec::new(),
+            args: declared.args.iter().map(|arg| arg.substitute(&self_type)).collect(),
+            return_type: declared.return_type.substitute(&self_type),
+        };
+
+        node.trait_name = Some(trait_name);
+        Some(signature)
+    }
+
+    fn check_variant_call(&mut self, node: &mut VariantCallNode, expected: Option<&Type>) -> Option<Type> {
+        let payload = self.variant_payload(&node.enum_name, &node.variant, node.span)?;
+        let type_params = self.enums[&node.enum_name].type_params.clone();
+
+        if node.args.len() != payload.len() {
+            self.error(CheckErrorKind::PayloadCount {
+                variant: format!("{}::{}", node.enum_name, node.variant),
+                expected: payload.len(),
+                found: node.args.len(),
+            }, node.span);
+        }
+
+        let values = node.args.iter_mut().zip(&payload).collect();
+        let generic_type = Type::Named(node.enum_name.clone(), type_params.iter().cloned().map(Type::Param).collect());
+        node.type_args = self.check_generic_args(&type_params, values, &generic_type, expected, node.span)?;
+
+        Some(Type::Named(node.enum_name.clone(), node.type_args.clone()))
+    }
+
+    // Types of the values held by a variant, None if it is not declared
+    fn variant_payload(&mut self, enum_name: &str, variant: &str, span: Span) -> Option<Vec<Type>> {
+        let payload = self.enums.get(enum_name).map(|signature| signature.variant(variant).map(<[Type]>::to_vec));
+
+        match payload {
+            Some(Some(payload)) => Some(payload),
+            Some(None) => {
+                self.error(CheckErrorKind::UndefinedVariant {
+                    enum_name: enum_name.to_string(),
+                    variant: variant.to_string(),
+                }, span);
+                None
+            }
+            None => {
+                self.error(CheckErrorKind::UndefinedType(enum_name.to_string()), span);
+                None
+            }
+        }
+    }
+
+    // Arms must cover every possible value. Used as a value (`as_value`), the
+    // arms must all have a value of the same type, which is returned
+    fn check_match(&mut self, node: &mut MatchNode, as_value: bool, expected: Option<&Type>) -> Option<Type> {
+        let value_type = self.check_expr(&mut node.value, None);
+        let mut arms_type: Option<Type> = None;
+        let mut patterns_valid = true;
+
+        for arm in &mut node.arms {
+            // The scope of the bindings of the pattern
+            self.symbols_mut().push_scope();
+
+            if let Some(value_type) = &value_type {
+                let errors = self.errors.len();
+                self.check_pattern(&mut arm.pattern, value_type, VariableKind::Let);
+                patterns_valid &= self.errors.len() == errors;
+            }
+
+            if as_value {
+                let arm_type = self.check_block_value(&mut arm.body, arms_type.as_ref().or(expected));
+
+                match (&arms_type, arm_type) {
+                    (Some(expected), Some(found)) if *expected != found => {
+                        self.error(CheckErrorKind::Mismatch { expected: expected.clone(), found }, arm.body.span);
+                    }
+                    (None, found) => arms_type = found,
+                    _ => {}
+                }
+            } else {
+                self.check_block(&mut arm.body);
+            }
+
+            self.symbols_mut().pop_scope();
+        }
+
+        // Patterns are only known to be meaningful if they are all valid
+        if let (Some(value_type), true) = (&value_type, patterns_valid) {
+            let patterns = node.arms.iter().map(|arm| &arm.pattern).collect::<Vec<_>>();
+            let missing = missing_patterns(&self.enums, &patterns, value_type);
+
+            if !missing.is_empty() {
+                self.error(CheckErrorKind::NonExhaustive(missing), node.value.span());
+            }
+        }
+
+        arms_type
+    }
+
+    // Bindings of the pattern are declared in the current scope
+    fn check_pattern(&mut self, pattern: &mut Pattern, expected: &Type, kind: VariableKind) {
+        match pattern {
+            Pattern::Wildcard(_) => {}
+            Pattern::Binding(name, _) => self.symbols_mut().declare(name, Symbol {
+                var_type: expected.clone(),
+                kind,
+            }),
+            Pattern::Tuple(elements, span) => match expected {
+                Type::Tuple(types) if types.len() == elements.len() => {
+                    for (element, element_type) in elements.iter_mut().zip(types) {
+                        self.check_pattern(element, element_type, kind);
+                    }
+                }
+                _ => self.error(CheckErrorKind::TuplePattern {
+                    expected: expected.clone(),
+                    found: elements.len(),
+                }, *span),
+            },
+            Pattern::Literal(expr) => self.check_typed(expr, expected),
+            Pattern::Variant { enum_name, variant, fields, span } => {
+                let args = match expected {
+                    Type::Named(name, args) if name == enum_name => args,
+                    _ => {
+                        let found = Type::Named(enum_name.clone(), Vec::new());
+                        self.error(CheckErrorKind::Mismatch { expected: expected.clone(), found }, *span);
+                        return;
+                    }
+                };
+
+                let payload = match self.variant_payload(enum_name, variant, *span) {
+                    Some(payload) => payload,
+                    None => return,
+                };
+
+                if fields.len() != payload.len() {
+                    self.error(CheckErrorKind::PayloadCount {
+                        variant: format!("{}::{}", enum_name, variant),
+                        expected: payload.len(),
+                        found: fields.len(),
+                    }, *span);
+                    return;
+                }
+
+                let bindings = bind(&self.enums[enum_name.as_str()].type_params, args);
+                for (field, field_type) in fields.iter_mut().zip(&payload) {
+                    self.check_pattern(field, &field_type.substitute(&bindings), kind);
+                }
+            }
+        }
+    }
+}
+
+// Error for declaring `item` if `previous` declares the same name. Structs,
+// enums and traits share their names
+fn redeclaration(previous: &Item, item: &Item) -> Option<CheckErrorKind> {
+    let type_name = |item: &Item| match item {
+        Item::Struct(node) => Some(node.name.clone()),
+        Item::Enum(node) => Some(node.name.clone()),
+        Item::Trait(node) => Some(node.name.clone()),
+        _ => None,
+    };
+
+    match (previous, item) {
+        (Item::Function(previous), Item::Function(node)) if previous.name == node.name =>
+            Some(CheckErrorKind::DuplicateFunction(node.name.clone())),
+        (Item::Variable(previous), Item::Variable(node)) if previous.name == node.name =>
+            Some(CheckErrorKind::DuplicateGlobal(node.name.clone())),
+        _ => match (type_name(previous), type_name(item)) {
+            (Some(previous), Some(name)) if previous == name => Some(CheckErrorKind::DuplicateType(name)),
+            _ => None,
+        },
+    }
+}
+
+// Variable modified by assigning to a target, which is either the
+// variable itself or one of its fields or elements
+fn assigned_variable(target: &Expr) -> Option<&VariableCallNode> {
+    match target {
+        Expr::VariableCall(node) => Some(node),
+        Expr::Field(node) => assigned_variable(&node.expr),
+        Expr::Index(node) => assigned_variable(&node.expr),
+        _ => None,
+    }
+}
+
+// Whether every path through the block ends with a 'return'
+fn block_returns(node: &BlockNode) -> bool {
+    node.nodes.iter().any(stmt_returns)
+}
+
+fn stmt_returns(stmt: &Stmt) -> bool {
+    match stmt {
+        Stmt::Statement(node) => node.stat_type == Statement::Return,
+        Stmt::Block(node) => block_returns(node),
+        Stmt::If(node) => if_returns(node),
+        // The arms of a 'match' cover every value
+        Stmt::Match(node) => !node.arms.is_empty() && node.arms.iter().all(|arm| block_returns(&arm.body)),
+        // Only a 'loop' that is never broken out of does not end normally
+        Stmt::Loop(node) => matches!(node.kind, LoopKind::Infinite)
+            && !block_breaks(&node.block, &node.label, false),
+        _ => false,
+    }
+}
+
+// Whether a 'break' in the block exits the loop labeled `label`, `nested` tells
+// whether the block belongs to another loop inside of it
+fn block_breaks(node: &BlockNode, label: &Option<String>, nested: bool) -> bool {
+    node.nodes.iter().any(|stmt| stmt_breaks(stmt, label, nested))
+}
+
+fn stmt_breaks(stmt: &Stmt, label: &Option<String>, nested: bool) -> bool {
+    match stmt {
+        Stmt::Statement(node) if node.stat_type == Statement::Break => match &node.label {
+            Some(_) => node.label == *label,
+            None => !nested,
+        },
+        Stmt::Block(node) => block_breaks(node, label, nested),
+        Stmt::If(node) => if_breaks(node, label, nested),
+        Stmt::Match(node) => node.arms.iter().any(|arm| block_breaks(&arm.body, label, nested)),
+        Stmt::Loop(node) => block_breaks(&node.block, label, true),
+        _ => false,
+    }
+}
+
+fn if_breaks(node: &IfNode, label: &Option<String>, nested: bool) -> bool {
+    block_breaks(&node.then_block, label, nested) || match &node.else_branch {
+        Some(ElseBranch::If(else_if)) => if_breaks(else_if, label, nested),
+        Some(ElseBranch::Block(block)) => block_breaks(block, label, nested),
+        None => false,
+    }
+}
+
+// An 'if' returns if all of its branches do, which requires an 'else'
+fn if_returns(node: &IfNode) -> bool {
+    block_returns(&node.then_block) && match &node.else_branch {
+        Some(ElseBranch::If(else_if)) => if_returns(else_if),
+        Some(ElseBranch::Block(block)) => block_returns(block),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::lexer::Lexer;
+    use crate::parser::parser::Parser;
+
+    // Kinds of the errors reported for a program
+    fn errors(source: &str) -> Vec<CheckErrorKind> {
+        let tokens = Lexer::new().lex(source.to_string()).unwrap().clone();
+        let mut tree = Parser::new(tokens).parse().unwrap();
+
+        match Checker::new().check(&mut tree) {
+            Ok(()) => Vec::new(),
+            Err(errors) => errors.into_iter().map(|err| err.kind).collect(),
+        }
+    }
+
+    // Checks the inputs of a REPL session, returning the errors of each one
+    fn input_errors(inputs: &[&str]) -> Vec<Vec<CheckErrorKind>> {
+        let mut checker = Checker::new();
+
+        inputs.iter().map(|source| {
+            let tokens = Lexer::new().lex(source.to_string()).unwrap().clone();
+            let mut errors = Vec::new();
+
+            for mut input in Parser::new(tokens).parse_interactive().unwrap() {
+                match checker.check_input(&mut input) {
+                    Ok(()) => checker.declare_pending(),
+                    Err(found) => errors.extend(found.into_iter().map(|err| err.kind)),
+                }
+            }
+            errors
+        }).collect()
+    }
+
+    #[test]
+    fn declared_types() {
+        assert_eq!(errors("
+            var g :: Int64 = True
+            fcn f(x :: Int64) -> Bool {
+                return x
+            }
+            fcn main() -> Int64 {
+                var s :: String = 'c'
+                return f(1)
+            }
+        "), [
+            CheckErrorKind::Mismatch { expected: Type::Int64, found: Type::Bool },
+            CheckErrorKind::Mismatch { expected: Type::Bool, found: Type::Int64 },
+            CheckErrorKind::Mismatch { expected: Type::String, found: Type::Char },
+            CheckErrorKind::Mismatch { expected: Type::Int64, found: Type::Bool },
+        ]);
+    }
+
+    #[test]
+    fn undefined_names_and_calls() {
+        assert_eq!(errors("
+            fcn f(x :: Int64, y :: Bool) -> Int64 {
+                return x
+            }
+            fcn main() -> Int64 {
+                var a = f(1)
+                var b = f(1, 2)
+                var c = g()
+                return y
+            }
+        "), [
+            CheckErrorKind::ArgumentCount { name: "f".to_string(), expected: 2, found: 1 },
+            CheckErrorKind::Mismatch { expected: Type::Bool, found: Type::Int64 },
+            CheckErrorKind::UndefinedFunction("g".to_string()),
+            CheckErrorKind::UndefinedVariable("y".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn returns() {
+        assert_eq!(errors("
+            fcn f(x :: Int64) -> Int64 {
+                if x > 0 {
+                    return 1
+                }
+            }
+            fcn main() -> Int64 {
+                return 1 + True
+            }
+        "), [
+            CheckErrorKind::MissingReturn("f".to_string()),
+            CheckErrorKind::MismatchedOperands { operator: "+".to_string(), left: Type::Int64, right: Type::Bool },
+        ]);
+    }
+
+    #[test]
+    fn literal_ranges() {
+        assert_eq!(errors("
+            fcn main() -> Int64 {
+                var a :: Int8 = 127
+                var b :: Int8 = -128
+                var c :: Int8 = 128
+                var d :: Int8 = -129
+                var e :: UInt8 = 0xFF
+                var f :: UInt8 = -1
+                var g :: Float32 = 1
+                var h :: Int64 = 1.5
+                return 9_223_372_036_854_775_808
+            }
+        "), [
+            CheckErrorKind::LiteralOutOfRange(Type::Int8),
+            CheckErrorKind::LiteralOutOfRange(Type::Int8),
+            CheckErrorKind::LiteralOutOfRange(Type::UInt8),
+            CheckErrorKind::Mismatch { expected: Type::Int64, found: Type::Float64 },
+            CheckErrorKind::LiteralOutOfRange(Type::Int64),
+        ]);
+    }
+
+    #[test]
+    fn conditions() {
+        assert_eq!(errors("
+            fcn main() -> Int64 {
+                if 1 {
+                    return 1
+                }
+                var a = if True { 1 }
+                var b = if True { 1 } else { False }
+                var c = if True { return 1 } else { 2 }
+                return 0
+            }
+        "), [
+            CheckErrorKind::Mismatch { expected: Type::Bool, found: Type::Int64 },
+            CheckErrorKind::MissingElse,
+            CheckErrorKind::Mismatch { expected: Type::Int64, found: Type::Bool },
+            CheckErrorKind::StatementInExpression("return".to_string()),
+            // The branch then ends without a value
+            CheckErrorKind::MissingValue,
+        ]);
+    }
+
+    #[test]
+    fn loops() {
+        assert_eq!(errors("
+            fcn main() -> Int64 {
+                break
+                'outer: loop {
+                    while 1 {
+                        continue 'inner
+                    }
+                    break 'outer
+                }
+                for i in 0..True {
+                    i = 2
+                }
+                for c in 'a'..'z' {
+                }
+                return 0
+            }
+        "), [
+            CheckErrorKind::OutsideLoop("break".to_string()),
+            CheckErrorKind::Mismatch { expected: Type::Bool, found: Type::Int64 },
+            CheckErrorKind::UndefinedLabel("inner".to_string()),
+            CheckErrorKind::Mismatch { expected: Type::Int64, found: Type::Bool },
+            CheckErrorKind::AssignToImmutable("i".to_string()),
+            CheckErrorKind::InvalidRange(Type::Char),
+        ]);
+    }
+
+    #[test]
+    fn assignments() {
+        assert_eq!(errors("
+            fcn f() -> Int64 {
+                return 1
+            }
+            fcn main() -> Int64 {
+                var x = 1
+                x = True
+                y = 2
+                f() = 3
+                var s = \"a\"
+                s -= \"b\"
+                return x
+            }
+        "), [
+            CheckErrorKind::Mismatch { expected: Type::Int64, found: Type::Bool },
+            CheckErrorKind::UndefinedVariable("y".to_string()),
+            CheckErrorKind::InvalidAssignTarget,
+            CheckErrorKind::InvalidOperand { operator: "-=".to_string(), found: Type::String },
+        ]);
+    }
+
+    #[test]
+    fn immutable_bindings() {
+        assert_eq!(errors("
+            const LIMIT :: Int64 = 10 * 2
+            const DOUBLE = LIMIT * 2
+            var counter = 0
+            const WRONG = counter + 1
+            fcn f(x :: Int64) -> Int64 {
+                x = 2
+                return x
+            }
+            fcn main() -> Int64 {
+                let a = 1
+                a += 1
+                LIMIT = 3
+                counter = 4
+                const LOCAL = 5
+                return a
+            }
+        "), [
+            CheckErrorKind::NonConstant,
+            CheckErrorKind::AssignToImmutable("x".to_string()),
+            CheckErrorKind::AssignToImmutable("a".to_string()),
+            CheckErrorKind::AssignToImmutable("LIMIT".to_string()),
+            CheckErrorKind::LocalConst,
+        ]);
+    }
+
+    #[test]
+    fn inferred_types() {
+        let tokens = Lexer::new().lex("
+            fcn main() -> Int64 {
+                var small :: Int8 = 1
+                var a = 1
+                var b = 2.5
+                var c = small + 1
+                var d = 1 < 2
+                var e = \"e\"
+                var f = 1 + small
+                return a
+            }
+        ".to_string()).unwrap().clone();
+        let mut tree = Parser::new(tokens).parse().unwrap();
+        Checker::new().check(&mut tree).unwrap();
+
+        let types = match &tree.nodes[..] {
+            [Item::Function(main)] => main.block.nodes.iter().filter_map(|node| match node {
+                Stmt::Variable(node) => node.var_type.clone(),
+                _ => None,
+            }).collect::<Vec<_>>(),
+            nodes => panic!("unexpected items {:?}", nodes),
+        };
+        assert_eq!(types, [Type::Int8, Type::Int64, Type::Float64, Type::Int8, Type::Bool, Type::String, Type::Int8]);
+    }
+
+    #[test]
+    fn logical_and_comparison_operators() {
+        assert_eq!(errors("
+            fcn main() -> Int64 {
+                var a = 1 and True
+                var b = not 1
+                var c = True < False
+                var d = 1 == 'c'
+                var e = \"a\" < \"b\" or 'a' != 'b'
+                return 0
+            }
+        "), [
+            // Operands of logical operators are expected to be booleans
+            CheckErrorKind::Mismatch { expected: Type::Bool, found: Type::Int64 },
+            CheckErrorKind::InvalidOperand { operator: "!".to_string(), found: Type::Int64 },
+            CheckErrorKind::InvalidOperand { operator: "<".to_string(), found: Type::Bool },
+            CheckErrorKind::MismatchedOperands { operator: "==".to_string(), left: Type::Int64, right: Type::Char },
+        ]);
+    }
+
+    #[test]
+    fn bitwise_operands_must_be_integers() {
+        assert_eq!(errors("
+            fcn main() -> Int64 {
+                var a = True & False
+                var b = 1.5 | 2.5
+                var c = ~'c'
+                var d :: UInt8 = 1
+                var e = d ^ 1
+                var g = 2
+                var f = d ^ g
+                return 0
+            }
+        "), [
+            CheckErrorKind::InvalidOperand { operator: "&".to_string(), found: Type::Bool },
+            CheckErrorKind::InvalidOperand { operator: "|".to_string(), found: Type::Float64 },
+            CheckErrorKind::InvalidOperand { operator: "~".to_string(), found: Type::Char },
+            CheckErrorKind::MismatchedOperands { operator: "^".to_string(), left: Type::UInt8, right: Type::Int64 },
+        ]);
+    }
+
+    #[test]
+    fn unsigned_integers() {
+        assert_eq!(errors("
+            fcn main() -> Int64 {
+                var a :: UInt16 = 65535
+                var b :: Int16 = 1
+                var c = a + b
+                var d = -a
+                var e :: UInt128 = 340282366920938463463374607431768211455
+                var f :: Int128 = -170141183460469231731687303715884105728
+                return 0
+            }
+        "), [
+            CheckErrorKind::MismatchedOperands { operator: "+".to_string(), left: Type::UInt16, right: Type::Int16 },
+            CheckErrorKind::InvalidOperand { operator: "-".to_string(), found: Type::UInt16 },
+        ]);
+    }
+
+    #[test]
+    fn casts() {
+        assert_eq!(errors("
+            fcn main() -> Int64 {
+                let a = 300 as UInt8
+                let b = 'a' as Int32 + True as Int32
+                let c :: UInt8 = 65
+                let d = c as Char
+                let e = 1.5 as Bool
+                let f = 65 as Char
+                let g = True as Float64
+                let h = -a as Int8
+                return a as Int64 + b as Int64
+            }
+        "), [
+            CheckErrorKind::InvalidCast { from: Type::Float64, to: Type::Bool },
+            CheckErrorKind::InvalidCast { from: Type::Int64, to: Type::Char },
+            CheckErrorKind::InvalidCast { from: Type::Bool, to: Type::Float64 },
+            CheckErrorKind::InvalidOperand { operator: "-".to_string(), found: Type::UInt8 },
+        ]);
+    }
+
+    #[test]
+    fn structs() {
+        assert_eq!(errors("
+            struct Point {
+                x :: Int64, y :: Int64
+            }
+
+            struct Line {
+                from :: Point
+                to :: Point
+                from :: Int8
+                width :: Thickness
+            }
+
+            fcn main() -> Int64 {
+                let p = Point { x: 1, y: 2 }
+                var q = Point { x: 1 }
+                let r = Point { x: 1, y: 2, z: 3 }
+                p.x = 3
+                q.y = p.z
+                return p.x + q.y
+            }
+        "), [
+            CheckErrorKind::DuplicateField("from".to_string()),
+            CheckErrorKind::UndefinedType("Thickness".to_string()),
+            CheckErrorKind::MissingField { name: "Point".to_string(), field: "y".to_string() },
+            CheckErrorKind::UndefinedField { found: Type::Named("Point".to_string(), Vec::new()), field: "z".to_string() },
+            CheckErrorKind::AssignToImmutable("p".to_string()),
+            CheckErrorKind::UndefinedField { found: Type::Named("Point".to_string(), Vec::new()), field: "z".to_string() },
+        ]);
+    }
+
+    #[test]
+    fn enums() {
+        assert_eq!(errors("
+            enum Shape {
+                Circle(Float64)
+                Rect(Float64, Float64)
+                Circle
+            }
+
+            fcn main() -> Int64 {
+                let a = Shape::Rect(1)
+                let b = Shape::Square(2.0)
+                return 0
+            }
+        "), [
+            CheckErrorKind::DuplicateVariant("Circle".to_string()),
+            CheckErrorKind::PayloadCount { variant: "Shape::Rect".to_string(), expected: 2, found: 1 },
+            CheckErrorKind::UndefinedVariant { enum_name: "Shape".to_string(), variant: "Square".to_string() },
+        ]);
+    }
+
+    #[test]
+    fn non_exhaustive_matches() {
+        assert_eq!(errors("
+            enum Shape {
+                Circle(Float64)
+                Rect(Float64, Float64)
+                Empty
+            }
+
+            fcn main() -> Int64 {
+                let s = Shape::Empty
+                let a = match s {
+                    Shape::Circle(_) => 1
+                }
+                let b = match (s, True) {
+                    (Shape::Empty, _) => 1
+                    (_, False) => 2
+                    (Shape::Rect(_, _), True) => 3
+                }
+                let c = match 3 {
+                    0 => 0
+                    1 => 1
+                }
+                let d = match s {
+                    Shape::Circle(_) => 1
+                    _ => 2
+                }
+                return a + b + c + d
+            }
+        "), [
+            CheckErrorKind::NonExhaustive(vec!["Shape::Rect(_, _)".to_string(), "Shape::Empty".to_string()]),
+            CheckErrorKind::NonExhaustive(vec!["(Shape::Circle(_), True)".to_string()]),
+            CheckErrorKind::NonExhaustive(vec!["_".to_string()]),
+        ]);
+    }
+
+    #[test]
+    fn refutable_patterns() {
+        assert_eq!(errors("
+            enum Token {
+                Number(Int64)
+                End
+            }
+
+            fcn main() -> Int64 {
+                let (a, Token::Number(b)) = (1, Token::End)
+                let (c, True) = (2, False)
+                let (d, (e, _)) = (3, (4, 5))
+                return a + b + c + d + e
+            }
+        "), [
+            CheckErrorKind::RefutablePattern(vec!["(_, Token::End)".to_string()]),
+            CheckErrorKind::RefutablePattern(vec!["(_, False)".to_string()]),
+        ]);
+    }
+
+    #[test]
+    fn arrays_and_lists() {
+        assert_eq!(errors("
+            fcn main() -> Int64 {
+                let a = [1, 2, 3]
+                let l :: List<Int64> = [1]
+                var m :: List<Bool> = []
+                let e = []
+                let x = 5
+                a[0] = 1
+                l.push(2)
+                m.push(True)
+                a.push(4)
+                return x[0] + a[True] + a[1 as UInt8] + l.len()
+            }
+        "), [
+            CheckErrorKind::UnknownElementType,
+            CheckErrorKind::AssignToImmutable("a".to_string()),
+            CheckErrorKind::AssignToImmutable("l".to_string()),
+            CheckErrorKind::UndefinedMethod { found: Type::Array(Box::new(Type::Int64), 3), method: "push".to_string() },
+            CheckErrorKind::NotIndexable(Type::Int64),
+            CheckErrorKind::InvalidIndex(Type::Bool),
+        ]);
+    }
+
+    #[test]
+    fn tuples() {
+        assert_eq!(errors("
+            fcn divmod(a :: Int64, b :: Int64) -> (Int64, Int64) {
+                return (a / b, a % b)
+            }
+
+            fcn main() -> Int64 {
+                let t = (1, (True, 'c'))
+                let (q, r, s) = divmod(17, 5)
+                let (x, y) = 5
+                let u :: (Int64, Bool) = (1, 2)
+                t.0 = 2
+                return t.2
+            }
+        "), [
+            CheckErrorKind::TuplePattern { expected: Type::Tuple(vec![Type::Int64, Type::Int64]), found: 3 },
+            CheckErrorKind::TuplePattern { expected: Type::Int64, found: 2 },
+            CheckErrorKind::Mismatch {
+                expected: Type::Tuple(vec![Type::Int64, Type::Bool]),
+                found: Type::Tuple(vec![Type::Int64, Type::Int64]),
+            },
+            CheckErrorKind::AssignToImmutable("t".to_string()),
+            CheckErrorKind::UndefinedField {
+                found: Type::Tuple(vec![Type::Int64, Type::Tuple(vec![Type::Bool, Type::Char])]),
+                field: "2".to_string(),
+            },
+        ]);
+    }
+
+    #[test]
+    fn generics() {
+        assert_eq!(errors("
+            struct Pair<A, B> {
+                first :: A
+                second :: B
+            }
+
+            enum Option<T> {
+                Some(T)
+                None
+            }
+
+            fcn add<T>(a :: T, b :: T) -> T {
+                return a + b
+            }
+
+            fcn main() -> Int64 {
+                let p :: Pair<Int64> = Pair { first: 1, second: 2 }
+                let o = Option::None
+                let n :: Option<Int8> = Option::None
+                let q = Pair { first: 1, second: True }
+                let r :: Pair<Bool, Int64> = q
+                return add(1, 2)
+            }
+        "), [
+            CheckErrorKind::InvalidOperand { operator: "+".to_string(), found: Type::Param("T".to_string()) },
+            CheckErrorKind::TypeArgCount { name: "Pair".to_string(), expected: 2, found: 1 },
+            CheckErrorKind::Mismatch {
+                expected: Type::Named("Pair".to_string(), vec![Type::Int64]),
+                found: Type::Named("Pair".to_string(), vec![Type::Int64, Type::Int64]),
+            },
+            CheckErrorKind::CannotInfer("T".to_string()),
+            CheckErrorKind::Mismatch {
+                expected: Type::Named("Pair".to_string(), vec![Type::Bool, Type::Int64]),
+                found: Type::Named("Pair".to_string(), vec![Type::Int64, Type::Bool]),
+            },
+        ]);
+    }
+
+    #[test]
+    fn duplicate_items() {
+        assert_eq!(errors("
+            fcn f() -> Int64 {
+                return 1
+            }
+            fcn f() -> Int64 {
+                return 2
+            }
+            fcn main() -> Int64 {
+                return f()
+            }
+        "), [CheckErrorKind::DuplicateFunction("f".to_string())]);
+
+        assert_eq!(errors("
+            struct P { x :: Int64 }
+            struct P { y :: Int64 }
+            enum P { A }
+            trait P {
+                fcn p(self) -> Int64
+            }
+            fcn main() -> Int64 {
+                return 0
+            }
+        "), vec![CheckErrorKind::DuplicateType("P".to_string()); 3]);
+
+        assert_eq!(errors("
+            var g = 1
+            let g = True
+            fcn main() -> Int64 {
+                return 0
+            }
+        "), [CheckErrorKind::DuplicateGlobal("g".to_string())]);
+    }
+
+    #[test]
+    fn duplicate_arguments() {
+        assert_eq!(errors("
+            fcn f(x :: Int64, x :: Bool) -> Int64 {
+                return 1
+            }
+            trait T {
+                fcn t(self, y :: Int64, y :: Int64) -> Int64
+            }
+            fcn main() -> Int64 {
+                return 0
+            }
+        "), [CheckErrorKind::DuplicateArgument("y".to_string()), CheckErrorKind::DuplicateArgument("x".to_string())]);
+    }
+
+    #[test]
+    fn redefinition_in_repl() {
+        let errors = input_errors(&[
+            "fcn f() -> Int64 {\n    return 1\n}",
+            "fcn f() -> Bool {\n    return True\n}",
+            "struct P { x :: Int64 }",
+            "struct P { y :: Int64 }",
+            "var g = 1",
+            "var g = True",
+            "let b :: Bool = f() and g",
+        ]);
+        assert!(errors.iter().all(Vec::is_empty), "{:?}", errors);
+    }
+
+    #[test]
+    fn destructuring_in_repl() {
+        let errors = input_errors(&[
+            "let (a, (b, _)) = (1, (True, 'c'))",
+            "let c :: Bool = a == 1 and b",
+            "let (d, e) = 5",
+            "d",
+        ]);
+        assert_eq!(errors, [
+            vec![],
+            vec![],
+            vec![CheckErrorKind::TuplePattern { expected: Type::Int64, found: 2 }],
+            vec![CheckErrorKind::UndefinedVariable("d".to_string())],
+        ]);
+    }
+
+    #[test]
+    fn main_function() {
+        assert_eq!(errors("
+            fcn f() -> Int64 {
+                return 0
+            }
+        "), [CheckErrorKind::MissingMain]);
+
+        assert_eq!(errors("
+            fcn main(x :: Int64) -> Int64 {
+                return x
+            }
+        "), [CheckErrorKind::MainSignature]);
+
+        assert_eq!(errors("
+            fcn main<T>() -> Int64 {
+                return 0
+            }
+        "), [CheckErrorKind::MainSignature]);
+
+        assert_eq!(errors("
+            fcn main() -> Missing {
+                return 0
+            }
+        "), [CheckErrorKind::UndefinedType("Missing".to_string()), CheckErrorKind::Mismatch {
+            expected: Type::Named("Missing".to_string(), Vec::new()),
+            found: Type::Int64,
+        }]);
+    }
+
+    #[test]
+    fn shift_amount_has_its_own_type() {
+        assert_eq!(errors("
+            fcn main() -> Int64 {
+                let s :: UInt8 = 3
+                let y :: Int64 = 1 << s
+                var z = 200 << s
+                z >>= s
+                return y + z
+            }
+        "), []);
+    }
+
+    #[test]
+    fn shift_amount_literal_is_not_range_checked_against_the_value() {
+        assert_eq!(errors("
+            fcn f(x :: Int8) -> Int8 {
+                var y = x
+                y <<= 300
+                return x << 300
+            }
+            fcn main() -> Int8 {
+                return f(1)
+            }
+        "), []);
+    }
+
+    #[test]
+    fn shift_operands_must_be_integers() {
+        assert_eq!(errors("
+            fcn main() -> Int64 {
+                let x = 1.0
+                return 1 << x
+            }
+        "), [CheckErrorKind::InvalidOperand { operator: "<<".to_string(), found: Type::Float64 }]);
+    }
+
+    #[test]
+    fn trait_declarations_and_impls() {
+        assert_eq!(errors("
+            struct Point {
+                x :: Int64
+                y :: Int64
+            }
+
+            trait Shape {
+                fcn area(self) -> Float64
+                fcn larger(self, other :: Self) -> Bool
+                fcn origin() -> Point
+                fcn area(self) -> Int64
+            }
+
+            impl Shape for Point {
+                fcn area(self) -> Int64 {
+                    return self.x * self.y
+                }
+
+                fcn scale(self) -> Point {
+                    return self
+                }
+            }
+
+            impl<T> Show for Point {
+                fcn show(self) -> String {
+                    return \"point\"
+                }
+            }
+
+            fcn main() -> Int64 {
+                return 0
+            }
+        "), [
+            CheckErrorKind::MissingSelf("origin".to_string()),
+            CheckErrorKind::DuplicateMethod("area".to_string()),
+            CheckErrorKind::MethodSignature {
+                trait_name: "Shape".to_string(),
+                method: "area".to_string(),
+                expected: "fcn area(self) -> Float64".to_string(),
+            },
+            CheckErrorKind::ExtraMethod { trait_name: "Shape".to_string(), method: "scale".to_string() },
+            CheckErrorKind::MissingMethod { trait_name: "Shape".to_string(), method: "larger".to_string() },
+            CheckErrorKind::UnusedTypeParam("T".to_string()),
+            CheckErrorKind::UndefinedTrait("Show".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn method_calls_and_bounds() {
+        assert_eq!(errors("
+            trait Show {
+                fcn show(self) -> String
+            }
+
+            trait Debug {
+                fcn show(self) -> String
+                fcn debug(self) -> String
+            }
+
+            impl Show for Int64 {
+                fcn show(self) -> String {
+                    return \"int\"
+                }
+            }
+
+            impl Debug for Int64 {
+                fcn show(self) -> String {
+                    return \"int\"
+                }
+
+                fcn debug(self) -> String {
+                    return \"int\"
+                }
+            }
+
+            fcn describe<T: Show>(value :: T) -> String {
+                return value.show()
+            }
+
+            fcn main() -> Int64 {
+                let a = 1.show()
+                let b = True.debug()
+                let c = describe(2)
+                let d = describe(False)
+                let e = 1.print()
+                return 0
+            }
+        "), [
+            CheckErrorKind::AmbiguousMethod { method: "show".to_string(), traits: vec!["Debug".to_string(), "Show".to_string()] },
+            CheckErrorKind::MissingImpl { trait_name: "Debug".to_string(), found: Type::Bool },
+            CheckErrorKind::MissingImpl { trait_name: "Show".to_string(), found: Type::Bool },
+            CheckErrorKind::UndefinedMethod { found: Type::Int64, method: "print".to_string() },
+        ]);
+    }
+
+    #[test]
+    fn conflicting_impls() {
+        let program = |first: &str, second: &str| format!("
+            struct Pair<A, B> {{
+                first :: A
+                second :: B
+            }}
+            trait Tr {{
+                fcn get(self) -> Int64
+            }}
+            impl{} {{
+                fcn get(self) -> Int64 {{
+                    return 1
+                }}
+            }}
+            impl{} {{
+                fcn get(self) -> Int64 {{
+                    return 2
+                }}
+            }}
+            fcn main() -> Int64 {{
+                return 0
+            }}
+        ", first, second);
+        let conflict = |found: Type| vec![CheckErrorKind::ConflictingImpl { trait_name: "Tr".to_string(), found }];
+        let pair = |first: Type, second: Type| Type::Named("Pair".to_string(), vec![first, second]);
+        let param = |name: &str| Type::Param(name.to_string());
+
+        // Each impl applies to Pair<Int64, Int64>, whichever is declared first
+        assert_eq!(errors(&program("<T> Tr for Pair<T, Int64>", "<T> Tr for Pair<Int64, T>")),
+                   conflict(pair(Type::Int64, param("T"))));
+        assert_eq!(errors(&program("<T> Tr for Pair<Int64, T>", "<T> Tr for Pair<T, Int64>")),
+                   conflict(pair(param("T"), Type::Int64)));
+        assert_eq!(errors(&program("<T> Tr for Pair<T, T>", " Tr for Pair<Int64, Int64>")),
+                   conflict(pair(Type::Int64, Type::Int64)));
+
+        assert_eq!(errors(&program("<T> Tr for Pair<T, T>", " Tr for Pair<Int64, Bool>")), vec![]);
+        assert_eq!(errors(&program("<T> Tr for Pair<T, Bool>", "<T> Tr for Pair<T, Int64>")), vec![]);
+    }
+}
@@ -0,0 +1,242 @@
+use std::fmt;
+
+use crate::diagnostics::{diagnostic::Diagnostic, span::Span};
+use crate::parser::types::Type;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum CheckErrorKind {
+    Mismatch { expected: Type, found: Type },
+    UndefinedVariable(String),
+    UndefinedFunction(String),
+    ArgumentCount { name: String, expected: usize, found: usize },
+    InvalidOperand { operator: String, found: Type },
+    MismatchedOperands { operator: String, left: Type, right: Type },
+    LiteralOutOfRange(Type),
+    MissingReturn(String),
+    MissingMain,
+    MainSignature,
+    ReturnOutsideFunction,
+    MissingElse,
+    MissingValue,
+    StatementInExpression(String),
+    OutsideLoop(String),
+    UndefinedLabel(String),
+    InvalidRange(Type),
+    InvalidAssignTarget,
+    AssignToImmutable(String),
+    NonConstant,
+    LocalConst,
+    InvalidCast { from: Type, to: Type },
+    UndefinedType(String),
+    UndefinedField { found: Type, field: String },
+    DuplicateFunction(String),
+    DuplicateType(String),
+    DuplicateGlobal(String),
+    DuplicateArgument(String),
+    DuplicateField(String),
+    MissingField { name: String, field: String },
+    UndefinedVariant { enum_name: String, variant: String },
+    DuplicateVariant(String),
+    PayloadCount { variant: String, expected: usize, found: usize },
+    NonExhaustive(Vec<String>),
+    NotIndexable(Type),
+    InvalidIndex(Type),
+    UnknownElementType,
+    UndefinedMethod { found: Type, method: String },
+    TuplePattern { expected: Type, found: usize },
+    RefutablePattern(Vec<String>),
+    TypeArgCount { name: String, expected: usize, found: usize },
+    CannotInfer(String),
+    UndefinedTrait(String),
+    MissingSelf(String),
+    DuplicateMethod(String),
+    MissingMethod { trait_name: String, method: String },
+    ExtraMethod { trait_name: String, method: String },
+    MethodSignature { trait_name: String, method: String, expected: String },
+    UnusedTypeParam(String),
+    ConflictingImpl { trait_name: String, found: Type },
+    MissingImpl { trait_name: String, found: Type },
+    AmbiguousMethod { method: String, traits: Vec<String> },
+}
+
+impl CheckErrorKind {
+    // Message displayed under the offending code
+    pub fn label(&self) -> String {
+        match self {
+            Self::Mismatch { expected, found } => format!("expected {}, found {}", expected, found),
+            Self::UndefinedVariable(_) | Self::UndefinedFunction(_) =>
+                "not declared in this scope".to_string(),
+            Self::ArgumentCount { expected, .. } => format!("expected {} argument(s)", expected),
+            Self::InvalidOperand { found, .. } => format!("this is {}", found),
+            Self::MismatchedOperands { operator, left, right } =>
+                format!("{} {} {}", left, operator, right),
+            Self::LiteralOutOfRange(t) => match t.integer_range() {
+                Some((min, max)) => format!("the range of {} is {}..={}", t, min, max),
+                None => format!("does not fit in {}", t),
+            },
+            Self::MissingReturn(_) => "not every path returns a value".to_string(),
+            Self::MissingMain => String::new(),
+            Self::MainSignature => "expected fcn main() -> <type>".to_string(),
+            Self::ReturnOutsideFunction => String::new(),
+            Self::MissingElse => "this 'if' has no 'else' branch".to_string(),
+            Self::MissingValue => "expected an expression at the end of this block".to_string(),
+            Self::StatementInExpression(_) => "the branch of an 'if' expression".to_string(),
+            Self::OutsideLoop(_) => String::new(),
+            Self::UndefinedLabel(_) => "no enclosing loop has this label".to_string(),
+            Self::InvalidRange(t) => format!("this is {}", t),
+            Self::InvalidAssignTarget => "cannot be assigned to".to_string(),
+            Self::AssignToImmutable(_) => "not declared with 'var'".to_string(),
+            Self::NonConstant => "not known before running the program".to_string(),
+            Self::LocalConst => "use 'let' instead".to_string(),
+            Self::InvalidCast { from, .. } => format!("this is {}", from),
+            Self::UndefinedType(_) => "not declared".to_string(),
+            Self::UndefinedField { found, .. } => format!("not a field of {}", found),
+            Self::DuplicateFunction(_) | Self::DuplicateType(_) | Self::DuplicateGlobal(_)
+                | Self::DuplicateArgument(_) => "declared more than once".to_string(),
+            Self::DuplicateField(_) => "given more than once".to_string(),
+            Self::MissingField { field, .. } => format!("'{}' is not given", field),
+            Self::UndefinedVariant { .. } => "not declared in this enum".to_string(),
+            Self::DuplicateVariant(_) => "declared more than once".to_string(),
+            Self::PayloadCount { expected, .. } => format!("expected {} value(s)", expected),
+            Self::NonExhaustive(patterns) | Self::RefutablePattern(patterns) if patterns.len() > 3 =>
+                format!("{} and {} more not covered", patterns[..3].join(", "), patterns.len() - 3),
+            Self::NonExhaustive(patterns) | Self::RefutablePattern(patterns) =>
+                format!("{} not covered", patterns.join(", ")),
+            Self::NotIndexable(t) | Self::InvalidIndex(t) => format!("this is {}", t),
+            Self::UnknownElementType => "give the declaration a type".to_string(),
+            Self::UndefinedMethod { found, .. } => format!("not a method of {}", found),
+            Self::TypeArgCount { expected, .. } => format!("expected {} type argument(s)", expected),
+            Self::CannotInfer(_) => "give the declaration a type".to_string(),
+            Self::UndefinedTrait(_) => "not declared".to_string(),
+            Self::MissingSelf(_) => "no 'self' argument".to_string(),
+            Self::DuplicateMethod(_) => "declared more than once".to_string(),
+            Self::MissingMethod { method, .. } => format!("'{}' is not implemented", method),
+            Self::ExtraMethod { .. } => "not declared in the trait".to_string(),
+            Self::MethodSignature { expected, .. } => format!("expected {}", expected),
+            Self::UnusedTypeParam(_) => "not used by the implemented type".to_string(),
+            Self::ConflictingImpl { .. } => "already implemented".to_string(),
+            Self::MissingImpl { trait_name, found } => format!("no impl of {} for {}", trait_name, found),
+            Self::AmbiguousMethod { traits, .. } => format!("declared by {}", traits.join(", ")),
+            Self::TuplePattern { expected, found } =>
+                format!("expected {}, found a tuple of {} elements", expected, found),
+        }
+    }
+
+    // Hint on how to fix the error, displayed below the code
+    pub fn note(&self) -> Option<&str> {
+        match self {
+            Self::MissingReturn(_) => Some("end the function with a 'return'"),
+            Self::MissingMain => Some("a program is run by calling its 'main' function"),
+            Self::NonExhaustive(_) => Some("add an arm for each missing pattern, or a '_' arm"),
+            Self::RefutablePattern(_) => Some("use a 'match' to handle the other values"),
+            Self::CannotInfer(_) | Self::UnknownElementType =>
+                Some("the type of the declaration is used when the value does not tell"),
+            Self::MissingImpl { .. } => Some("add an impl of the trait for the type"),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CheckErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Mismatch { .. } => write!(f, "Mismatched types"),
+            Self::UndefinedVariable(name) => write!(f, "Cannot find variable '{}'", name),
+            Self::UndefinedFunction(name) => write!(f, "Cannot find function '{}'", name),
+            Self::ArgumentCount { name, expected, found } =>
+                write!(f, "Function '{}' takes {} argument(s) but {} were supplied",
+                       name, expected, found),
+            Self::InvalidOperand { operator, found } =>
+                write!(f, "Operator '{}' cannot be applied to {}", operator, found),
+            Self::MismatchedOperands { operator, .. } =>
+                write!(f, "Mismatched operand types for '{}'", operator),
+            Self::LiteralOutOfRange(t) => write!(f, "Literal out of range for {}", t),
+            Self::MissingReturn(name) => write!(f, "Function '{}' may end without returning", name),
+            Self::MissingMain => write!(f, "The program has no 'main' function"),
+            Self::MainSignature => write!(f, "'main' cannot take arguments or type parameters"),
+            Self::ReturnOutsideFunction => write!(f, "'return' outside of a function"),
+            Self::MissingElse => write!(f, "'if' used as a value must have an 'else'"),
+            Self::MissingValue => write!(f, "Block has no value"),
+            Self::StatementInExpression(statement) =>
+                write!(f, "'{}' cannot be used inside an 'if' expression", statement),
+            Self::OutsideLoop(statement) => write!(f, "'{}' outside of a loop", statement),
+            Self::UndefinedLabel(label) => write!(f, "Cannot find label '{}", label),
+            Self::InvalidRange(_) => write!(f, "Range bounds must be integers"),
+            Self::InvalidAssignTarget => write!(f, "Invalid left-hand side of assignment"),
+            Self::AssignToImmutable(name) => write!(f, "Cannot assign to immutable variable '{}'", name),
+            Self::NonConstant => write!(f, "Constant initialized with a non-constant expression"),
+            Self::LocalConst => write!(f, "'const' can only be declared at the top level"),
+            Self::InvalidCast { from, to } => write!(f, "Cannot cast {} to {}", from, to),
+            Self::UndefinedType(name) => write!(f, "Cannot find type '{}'", name),
+            Self::UndefinedField { found, field } => write!(f, "{} has no field '{}'", found, field),
+            Self::DuplicateFunction(name) => write!(f, "Function '{}' is repeated", name),
+            Self::DuplicateType(name) => write!(f, "Type '{}' is repeated", name),
+            Self::DuplicateGlobal(name) => write!(f, "Global variable '{}' is repeated", name),
+            Self::DuplicateArgument(name) => write!(f, "Argument '{}' is repeated", name),
+            Self::DuplicateField(field) => write!(f, "Field '{}' is repeated", field),
+            Self::MissingField { name, field } => write!(f, "Missing field '{}' of {}", field, name),
+            Self::UndefinedVariant { enum_name, variant } =>
+                write!(f, "Enum '{}' has no variant '{}'", enum_name, variant),
+            Self::DuplicateVariant(variant) => write!(f, "Variant '{}' is repeated", variant),
+            Self::PayloadCount { variant, expected, found } =>
+                write!(f, "Variant '{}' holds {} value(s) but {} were supplied", variant, expected, found),
+            Self::NonExhaustive(_) => write!(f, "Non-exhaustive patterns in 'match'"),
+            Self::NotIndexable(t) => write!(f, "Cannot index into a value of type {}", t),
+            Self::InvalidIndex(_) => write!(f, "Indexes must be integers"),
+            Self::UnknownElementType => write!(f, "Cannot infer the type of an empty array"),
+            Self::UndefinedMethod { found, method } => write!(f, "{} has no method '{}'", found, method),
+            Self::TuplePattern { .. } => write!(f, "Mismatched types"),
+            Self::RefutablePattern(_) => write!(f, "Refutable pattern in a variable declaration"),
+            Self::TypeArgCount { name, expected, found } =>
+                write!(f, "Type '{}' takes {} type argument(s) but {} were supplied", name, expected, found),
+            Self::CannotInfer(param) => write!(f, "Cannot infer the type parameter '{}'", param),
+            Self::UndefinedTrait(name) => write!(f, "Cannot find trait '{}'", name),
+            Self::MissingSelf(method) => write!(f, "Method '{}' must take 'self' as its first argument", method),
+            Self::DuplicateMethod(method) => write!(f, "Method '{}' is repeated", method),
+            Self::MissingMethod { trait_name, method } =>
+                write!(f, "Missing method '{}' of trait '{}'", method, trait_name),
+            Self::ExtraMethod { trait_name, method } => write!(f, "Trait '{}' has no method '{}'", trait_name, method),
+            Self::MethodSignature { trait_name, method, .. } =>
+                write!(f, "Method '{}' does not match its declaration in trait '{}'", method, trait_name),
+            Self::UnusedTypeParam(param) =>
+                write!(f, "Type parameter '{}' does not appear in the implemented type", param),
+            Self::ConflictingImpl { trait_name, found } =>
+                write!(f, "Conflicting implementations of trait '{}' for {}", trait_name, found),
+            Self::MissingImpl { trait_name, found } => write!(f, "{} does not implement trait '{}'", found, trait_name),
+            Self::AmbiguousMethod { method, .. } => write!(f, "Method '{}' is declared by several traits", method),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CheckError {
+    pub kind: CheckErrorKind,
+    pub span: Span,
+}
+
+impl CheckError {
+    pub fn new(kind: CheckErrorKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+}
+
+impl fmt::Display for CheckError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl From<&CheckError> for Diagnostic {
+    fn from(err: &CheckError) -> Self {
+        let diagnostic = match err.kind {
+            // Nothing in the program points to the missing function
+            CheckErrorKind::MissingMain => Diagnostic::error(&err.kind.to_string()),
+            _ => Diagnostic::error(&err.kind.to_string()).with_label(err.span, &err.kind.label()),
+        };
+
+        match err.kind.note() {
+            Some(note) => diagnostic.with_note(note),
+            None => diagnostic,
+        }
+    }
+}
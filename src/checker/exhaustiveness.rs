@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+use std::slice;
+
+use crate::checker::{generics::bind, symbols::EnumSignature};
+use crate::diagnostics::span::Span;
+use crate::parser::{tree::{Expr, Pattern}, types::Type};
+
+// Values which can be told apart by a pattern without looking at their payload
+enum Constructor<'a> {
+    // The payload is given with the type arguments of the enum substituted
+    Variant { enum_name: &'a str, name: &'a str, payload: Vec<Type> },
+    Bool(bool),
+    // The only constructor of a tuple type, its payload being the elements
+    Tuple(&'a [Type]),
+}
+
+impl Constructor<'_> {
+    fn payload(&self) -> &[Type] {
+        match self {
+            Constructor::Variant { payload, .. } => payload,
+            Constructor::Bool(_) => &[],
+            Constructor::Tuple(types) => types,
+        }
+    }
+
+    // Patterns of the payload if `pattern` only matches values built with
+    // this constructor
+    fn fields<'p>(&self, pattern: &'p Pattern) -> Option<Vec<&'p Pattern>> {
+        match (self, pattern) {
+            (Constructor::Variant { name, .. }, Pattern::Variant { variant, fields, .. })
+                if variant == name => Some(fields.iter().collect()),
+            (Constructor::Bool(value), Pattern::Literal(Expr::Bool(node)))
+                if node.value == *value => Some(Vec::new()),
+            (Constructor::Tuple(_), Pattern::Tuple(elements, _)) => Some(elements.iter().collect()),
+            _ => None,
+        }
+    }
+
+    // Pattern matching this constructor, `fields` being the patterns of its payload
+    fn describe(&self, fields: &[String]) -> String {
+        match self {
+            Constructor::Variant { enum_name, name, .. } if fields.is_empty() =>
+                format!("{}::{}", enum_name, name),
+            Constructor::Variant { enum_name, name, .. } =>
+                format!("{}::{}({})", enum_name, name, fields.join(", ")),
+            Constructor::Bool(true) => "True".to_string(),
+            Constructor::Bool(false) => "False".to_string(),
+            Constructor::Tuple(_) => format!("({})", fields.join(", ")),
+        }
+    }
+}
+
+// Patterns describing the values matched by none of `patterns`, such as
+// "Shape::Rect(_, _)", empty if the patterns cover every value of the type
+pub fn missing_patterns(enums: &HashMap<String, EnumSignature>, patterns: &[&Pattern],
+                        value_type: &Type) -> Vec<String> {
+    let rows = patterns.iter().map(|pattern| vec![*pattern]).collect();
+
+    uncovered(enums, rows, slice::from_ref(value_type))
+        .into_iter()
+        .filter_map(|witness| witness.into_iter().next())
+        .collect()
+}
+
+// Values whose columns are of `types` and which are matched by none of the rows,
+// each one described by a pattern per column. The first column is split by the
+// constructors of its type, or only wildcards can cover it if the type has none
+fn uncovered(enums: &HashMap<String, EnumSignature>, rows: Vec<Vec<&Pattern>>,
+             types: &[Type]) -> Vec<Vec<String>> {
+    // Nothing is matched, which is described without going through every constructor
+    if rows.is_empty() {
+        return vec![vec!["_".to_string(); types.len()]];
+    }
+
+    // No column left, a remaining row matches everything
+    let (first, rest) = match types.split_first() {
+        Some(split) => split,
+        None => return Vec::new(),
+    };
+
+    let constructors = constructors(enums, first);
+
+    // Without a constructor pattern in the column, splitting it would give the
+    // same result for every constructor, and would not end for a recursive enum
+    if constructors.is_empty() || rows.iter().all(|row| is_wildcard(row[0])) {
+        let rows = rows.iter()
+            .filter(|row| is_wildcard(row[0]))
+            .map(|row| row[1..].to_vec())
+            .collect();
+
+        return uncovered(enums, rows, rest).into_iter()
+            .map(|mut witness| {
+                witness.insert(0, "_".to_string());
+                witness
+            })
+            .collect();
+    }
+
+    // Values built with a constructor are matched by the rows starting with
+    // it, or with a wildcard which then matches its whole payload
+    let wildcard = Pattern::Wildcard(Span::default());
+
+    constructors.iter().flat_map(|constructor| {
+        let arity = constructor.payload().len();
+        let rows = rows.iter()
+            .filter_map(|row| {
+                let fields = match constructor.fields(row[0]) {
+                    Some(fields) => fields,
+                    None if is_wildcard(row[0]) => vec![&wildcard; arity],
+                    None => return None,
+                };
+                Some(fields.into_iter().chain(row[1..].iter().copied()).collect())
+            })
+            .collect();
+        let types = constructor.payload().iter().chain(rest).cloned().collect::<Vec<_>>();
+
+        uncovered(enums, rows, &types).into_iter().map(move |witness| {
+            let (fields, others) = witness.split_at(arity);
+            let mut witness = vec![constructor.describe(fields)];
+            witness.extend_from_slice(others);
+            witness
+        })
+    }).collect()
+}
+
+// Constructors of a type, empty if its values can only be told apart by
+// literals (numbers, strings...) or not at all (structs)
+fn constructors<'a>(enums: &'a HashMap<String, EnumSignature>, value_type: &'a Type) -> Vec<Constructor<'a>> {
+    match value_type {
+        Type::Bool => vec![Constructor::Bool(true), Constructor::Bool(false)],
+        Type::Named(enum_name, args) => match enums.get(enum_name) {
+            Some(signature) => {
+                let bindings = bind(&signature.type_params, args);
+                signature.variants.iter()
+                    .map(|(name, payload)| Constructor::Variant {
+                        enum_name,
+                        name,
+                        payload: payload.iter().map(|field_type| field_type.substitute(&bindings)).collect(),
+                    })
+                    .collect()
+            }
+            None => Vec::new(),
+        },
+        Type::Tuple(types) => vec![Constructor::Tuple(types)],
+        _ => Vec::new(),
+    }
+}
+
+fn is_wildcard(pattern: &Pattern) -> bool {
+    matches!(pattern, Pattern::Wildcard(_) | Pattern::Binding(..))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::tree::BoolNode;
+
+    fn enums() -> HashMap<String, EnumSignature> {
+        let mut enums = HashMap::new();
+        enums.insert("L".to_string(), EnumSignature {
+            type_params: Vec::new(),
+            variants: vec![
+                ("Cons".to_string(), vec![Type::Int64, Type::Named("L".to_string(), Vec::new())]),
+                ("Nil".to_string(), Vec::new()),
+            ],
+        });
+        enums.insert("Option".to_string(), EnumSignature {
+            type_params: vec!["T".to_string()],
+            variants: vec![
+                ("Some".to_string(), vec![Type::Param("T".to_string())]),
+                ("None".to_string(), Vec::new()),
+            ],
+        });
+        enums
+    }
+
+    fn wildcard() -> Pattern {
+        Pattern::Wildcard(Span::default())
+    }
+
+    fn variant(enum_name: &str, variant: &str, fields: Vec<Pattern>) -> Pattern {
+        Pattern::Variant {
+            enum_name: enum_name.to_string(),
+            variant: variant.to_string(),
+            fields,
+            span: Span::default(),
+        }
+    }
+
+    fn boolean(value: bool) -> Pattern {
+        Pattern::Literal(Expr::Bool(BoolNode { value, span: Span::default() }))
+    }
+
+    fn missing(patterns: &[Pattern], value_type: &Type) -> Vec<String> {
+        missing_patterns(&enums(), &patterns.iter().collect::<Vec<_>>(), value_type)
+    }
+
+    fn list() -> Type {
+        Type::Named("L".to_string(), Vec::new())
+    }
+
+    fn option(arg: Type) -> Type {
+        Type::Named("Option".to_string(), vec![arg])
+    }
+
+    #[test]
+    fn recursive_enum_with_wildcard() {
+        assert!(missing(&[wildcard()], &list()).is_empty());
+        assert!(missing(&[Pattern::Binding("x".to_string(), Span::default())], &list()).is_empty());
+    }
+
+    #[test]
+    fn recursive_enum_variants() {
+        let patterns = [variant("L", "Cons", vec![wildcard(), wildcard()]), variant("L", "Nil", Vec::new())];
+        assert!(missing(&patterns, &list()).is_empty());
+
+        let patterns = [variant("L", "Cons", vec![wildcard(), variant("L", "Nil", Vec::new())])];
+        assert_eq!(missing(&patterns, &list()), ["L::Cons(_, L::Cons(_, _))", "L::Nil"]);
+    }
+
+    #[test]
+    fn recursive_enum_in_tuple() {
+        let value_type = Type::Tuple(vec![Type::Int64, list()]);
+        assert!(missing(&[Pattern::Tuple(vec![wildcard(), wildcard()], Span::default())], &value_type).is_empty());
+    }
+
+    #[test]
+    fn generic_enum_payload() {
+        let patterns = [variant("Option", "Some", vec![boolean(true)]), variant("Option", "None", Vec::new())];
+        assert_eq!(missing(&patterns, &option(Type::Bool)), ["Option::Some(False)"]);
+
+        let patterns = [variant("Option", "Some", vec![wildcard()]), variant("Option", "None", Vec::new())];
+        assert!(missing(&patterns, &option(option(Type::Bool))).is_empty());
+
+        let patterns = [variant("Option", "Some", vec![variant("Option", "None", Vec::new())])];
+        assert_eq!(missing(&patterns, &option(option(Type::Int8))), ["Option::Some(Option::Some(_))", "Option::None"]);
+    }
+}
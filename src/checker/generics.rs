@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+
+use crate::parser::types::Type;
+
+// Types given to the type parameters of a generic declaration
+pub type Bindings = HashMap<String, Type>;
+
+pub fn bind(params: &[String], args: &[Type]) -> Bindings {
+    params.iter().cloned().zip(args.iter().cloned()).collect()
+}
+
+// Binds the type parameters of `pattern` so that it becomes `found`, false if
+// it cannot. The parameters `found` refers to belong to the declaration being
+// checked, they are compared like any other type
+pub fn unify(pattern: &Type, found: &Type, bindings: &mut Bindings) -> bool {
+    let unify_all = |patterns: &[Type], types: &[Type], bindings: &mut Bindings| {
+        patterns.len() == types.len()
+            && patterns.iter().zip(types).all(|(pattern, found)| unify(pattern, found, bindings))
+    };
+
+    match (pattern, found) {
+        (Type::Param(name), _) => match bindings.get(name) {
+            Some(bound) => bound == found,
+            None => {
+                bindings.insert(name.clone(), found.clone());
+                true
+            }
+        },
+        (Type::Named(name, args), Type::Named(found_name, found_args)) =>
+            name == found_name && unify_all(args, found_args, bindings),
+        (Type::Array(element_type, length), Type::Array(found_type, found_length)) =>
+            length == found_length && unify(element_type, found_type, bindings),
+        (Type::List(element_type), Type::List(found_type)) => unify(element_type, found_type, bindings),
+        (Type::Tuple(types), Type::Tuple(found_types)) => unify_all(types, found_types, bindings),
+        _ => pattern == found,
+    }
+}
+
+// `pattern` with its type parameters replaced, None if one of them is not bound yet
+pub fn resolve(pattern: &Type, bindings: &Bindings) -> Option<Type> {
+    if pattern.params().iter().all(|param| bindings.contains_key(*param)) {
+        Some(pattern.substitute(bindings))
+    } else {
+        None
+    }
+}
+
+// Whether some type is both an instance of `first` and of `second`, their type
+// parameters being unrelated even when they have the same name
+pub fn overlap(first: &Type, second: &Type) -> bool {
+    let rename = |pattern: &Type, prefix: &str| {
+        let fresh = pattern.params().into_iter()
+            .map(|param| (param.to_string(), Type::Param(format!("{}{}", prefix, param))))
+            .collect();
+        pattern.substitute(&fresh)
+    };
+
+    unify_both(&rename(first, "1."), &rename(second, "2."), &mut Bindings::new())
+}
+
+// Unlike `unify`, binds the type parameters of both types so that they become
+// the same, a parameter being bound at most once
+fn unify_both(first: &Type, second: &Type, bindings: &mut Bindings) -> bool {
+    let unify_all = |firsts: &[Type], seconds: &[Type], bindings: &mut Bindings| {
+        firsts.len() == seconds.len()
+            && firsts.iter().zip(seconds).all(|(first, second)| unify_both(first, second, bindings))
+    };
+
+    match (first, second) {
+        (Type::Param(name), other) | (other, Type::Param(name)) if bindings.contains_key(name) => {
+            let bound = bindings[name].clone();
+            unify_both(&bound, other, bindings)
+        }
+        (Type::Param(name), Type::Param(other)) if name == other => true,
+        (Type::Param(name), other) | (other, Type::Param(name)) => {
+            // `T` cannot be bound to `List<T>`, no type is its own element
+            if occurs(name, other, bindings) {
+                return false;
+            }
+            bindings.insert(name.clone(), other.clone());
+            true
+        }
+        (Type::Named(name, args), Type::Named(other_name, other_args)) =>
+            name == other_name && unify_all(args, other_args, bindings),
+        (Type::Array(element_type, length), Type::Array(other_type, other_length)) =>
+            length == other_length && unify_both(element_type, other_type, bindings),
+        (Type::List(element_type), Type::List(other_type)) => unify_both(element_type, other_type, bindings),
+        (Type::Tuple(types), Type::Tuple(other_types)) => unify_all(types, other_types, bindings),
+        _ => first == second,
+    }
+}
+
+// Whether `found`, once its bound parameters are replaced, refers to `param`
+fn occurs(param: &str, found: &Type, bindings: &Bindings) -> bool {
+    found.params().into_iter().any(|name| {
+        name == param || bindings.get(name).is_some_and(|bound| occurs(param, bound, bindings))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn param(name: &str) -> Type {
+        Type::Param(name.to_string())
+    }
+
+    fn named(name: &str, args: Vec<Type>) -> Type {
+        Type::Named(name.to_string(), args)
+    }
+
+    #[test]
+    fn overlapping_types() {
+        // Pair<T, Int64> and Pair<Int64, T> both have Pair<Int64, Int64> as instance
+        assert!(overlap(&named("Pair", vec![param("T"), Type::Int64]),
+                        &named("Pair", vec![Type::Int64, param("T")])));
+        assert!(overlap(&named("Pair", vec![param("T"), param("T")]),
+                        &named("Pair", vec![param("U"), Type::Bool])));
+        assert!(overlap(&Type::List(Box::new(param("T"))), &param("U")));
+    }
+
+    #[test]
+    fn disjoint_types() {
+        assert!(!overlap(&named("Pair", vec![param("T"), param("T")]),
+                         &named("Pair", vec![Type::Int64, Type::Bool])));
+        assert!(!overlap(&named("Pair", vec![param("T"), param("T")]),
+                         &named("Pair", vec![Type::List(Box::new(param("U"))), param("U")])));
+        assert!(!overlap(&Type::List(Box::new(param("T"))), &Type::Array(Box::new(param("T")), 2)));
+    }
+}
@@ -0,0 +1,6 @@
+#[allow(clippy::module_inception)]
+pub mod checker;
+pub mod symbols;
+pub mod error;
+pub mod exhaustiveness;
+pub mod generics;
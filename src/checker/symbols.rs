@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+
+use crate::parser::{
+    tree::{BoundNode, EnumNode, ImplNode, StructNode, TraitNode, VariableNode},
+    types::Type,
+    variables::VariableKind,
+};
+
+#[derive(Debug, Clone)]
+pub struct Symbol {
+    pub var_type: Type,
+    pub kind: VariableKind,
+}
+
+impl Symbol {
+    // None if the type of the variable could not be inferred
+    pub fn from_variable(node: &VariableNode) -> Option<Self> {
+        Some(Self { var_type: node.var_type.clone()?, kind: node.kind })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Signature {
+    pub type_params: Vec<String>,
+    // (parameter, trait) pairs
+    pub bounds: Vec<(String, String)>,
+    pub args: Vec<Type>,
+    pub return_type: Type,
+}
+
+// Method provided by the language on arrays and lists
+#[derive(Debug, Clone)]
+pub struct BuiltinMethod {
+    pub signature: Signature,
+    // Whether the method modifies its receiver, which must then be mutable
+    pub mutating: bool,
+}
+
+impl BuiltinMethod {
+    pub fn lookup(receiver: &Type, method: &str) -> Option<Self> {
+        let (args, return_type, mutating) = match (receiver, method) {
+            (Type::Array(..) | Type::List(_), "len") => (vec![], Type::Int64, false),
+            // Returns the new length
+            (Type::List(element_type), "push") => (vec![(**element_type).clone()], Type::Int64, true),
+            (Type::List(element_type), "pop") => (vec![], (**element_type).clone(), true),
+            _ => return None,
+        };
+
+        Some(Self { signature: Signature { type_params: Vec::new(), bounds: Vec::new(), args, return_type }, mutating })
+    }
+}
+
+// Fields of a struct, in declaration order
+#[derive(Debug, Clone)]
+pub struct StructSignature {
+    pub type_params: Vec<String>,
+    pub fields: Vec<(String, Type)>,
+}
+
+impl StructSignature {
+    pub fn from_struct(node: &StructNode) -> Self {
+        Self {
+            type_params: node.type_params.clone(),
+            fields: node.fields.iter().map(|field| (field.name.clone(), field.field_type.clone())).collect(),
+        }
+    }
+
+    pub fn field(&self, name: &str) -> Option<&Type> {
+        self.fields.iter().find(|(field, _)| field == name).map(|(_, field_type)| field_type)
+    }
+}
+
+// Variants of an enum along with their payload, in declaration order
+#[derive(Debug, Clone)]
+pub struct EnumSignature {
+    pub type_params: Vec<String>,
+    pub variants: Vec<(String, Vec<Type>)>,
+}
+
+impl EnumSignature {
+    pub fn from_enum(node: &EnumNode) -> Self {
+        Self {
+            type_params: node.type_params.clone(),
+            variants: node.variants.iter().map(|variant| (variant.name.clone(), variant.payload.clone())).collect(),
+        }
+    }
+
+    pub fn variant(&self, name: &str) -> Option<&[Type]> {
+        self.variants.iter().find(|(variant, _)| variant == name).map(|(_, payload)| payload.as_slice())
+    }
+}
+
+// Methods of a trait without their `self` argument, `Self` being the type
+// parameter standing for the implementing type
+#[derive(Debug, Clone)]
+pub struct TraitSignature {
+    pub methods: Vec<(String, Signature)>,
+}
+
+impl TraitSignature {
+    // Methods without a `self` argument are left out, they are reported
+    // when the trait is checked
+    pub fn from_trait(node: &TraitNode) -> Self {
+        let methods = node.methods.iter().filter_map(|method| {
+            let (receiver, args) = method.args.split_first()?;
+            if receiver.name != "self" {
+                return None;
+            }
+
+            Some((method.name.clone(), Signature {
+                type_params: Vec::new(),
+                bounds: Vec::new(),
+                args: args.iter().map(|arg| arg.var_type.clone()).collect(),
+                return_type: method.return_type.clone(),
+            }))
+        });
+
+        Self { methods: methods.collect() }
+    }
+
+    pub fn method(&self, name: &str) -> Option<&Signature> {
+        self.methods.iter().find(|(method, _)| method == name).map(|(_, signature)| signature)
+    }
+}
+
+// Implementation of a trait for the types matching `target`
+#[derive(Debug, Clone)]
+pub struct ImplSignature {
+    pub type_params: Vec<String>,
+    pub bounds: Vec<(String, String)>,
+    pub trait_name: String,
+    pub target: Type,
+}
+
+impl ImplSignature {
+    pub fn from_impl(node: &ImplNode) -> Self {
+        Self {
+            type_params: node.type_params.clone(),
+            bounds: bound_pairs(&node.bounds),
+            trait_name: node.trait_name.clone(),
+            target: node.target.clone(),
+        }
+    }
+}
+
+pub fn bound_pairs(bounds: &[BoundNode]) -> Vec<(String, String)> {
+    bounds.iter().map(|bound| (bound.param.clone(), bound.trait_name.clone())).collect()
+}
+
+// Stack of nested scopes, the innermost being the last one
+#[derive(Debug, Default)]
+pub struct SymbolTable {
+    scopes: Vec<HashMap<String, Symbol>>,
+}
+
+impl SymbolTable {
+    pub fn new() -> Self {
+        Self {
+            scopes: vec![HashMap::new()],
+        }
+    }
+
+    pub fn push_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    pub fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    // Declares a symbol in the innermost scope, shadowing any previous one
+    pub fn declare(&mut self, name: &str, symbol: Symbol) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_string(), symbol);
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Symbol> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    // Symbols declared in the outermost scope
+    pub fn into_outermost(mut self) -> HashMap<String, Symbol> {
+        self.scopes.swap_remove(0)
+    }
+}
@@ -1,26 +1,187 @@
+use std::fs;
 use std::io::{stdin, stdout, Write};
+use std::process::exit;
 
-use crate::lexer::lexer::Lexer;
-use crate::parser::parser::Parser;
+use renek_lang::checker::checker::Checker;
+use renek_lang::diagnostics::{diagnostic::Diagnostic, source::SourceFile};
+use renek_lang::interpreter::{interpreter::Interpreter, value::OverflowMode};
+use renek_lang::lexer::{lexer::Lexer, token::Token};
+use renek_lang::parser::parser::Parser;
+
+#[derive(Default)]
+pub struct Options {
+    // Print the tokens of every input
+    pub tokens: bool,
+    // Print the syntax tree of every input
+    pub ast: bool,
+    // Let integer arithmetic wrap around instead of stopping on overflow
+    pub wrapping: bool,
+    // Program to run instead of starting the REPL
+    pub file: Option<String>,
+}
+
+impl Options {
+    pub fn from_args(args: impl Iterator<Item = String>) -> Self {
+        let mut options = Self::default();
+
+        for arg in args {
+            match arg.as_str() {
+                "--tokens" => options.tokens = true,
+                "--ast" => options.ast = true,
+                "--wrapping" => options.wrapping = true,
+                _ if arg.starts_with("--") || options.file.is_some() => {
+                    println!("Usage: renek-lang [--tokens] [--ast] [--wrapping] [file]");
+                    exit(1);
+                }
+                _ => options.file = Some(arg),
+            }
+        }
+
+        options
+    }
+
+    fn interpreter(&self) -> Interpreter {
+        let overflow = if self.wrapping { OverflowMode::Wrap } else { OverflowMode::Trap };
+        Interpreter::new().with_overflow(overflow)
+    }
+}
+
+pub fn run_file(path: &str, options: &Options) {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) => {
+            println!("Could not read '{}': {}", path, err);
+            exit(1);
+        }
+    };
+
+    let source = SourceFile::new(path, content);
+
+    let tokens = match lex(&source, 0, options) {
+        Some(tokens) => tokens,
+        None => exit(1),
+    };
+
+    let mut tree = match Parser::new(tokens).parse() {
+        Ok(tree) => tree,
+        Err(errors) => {
+            for err in &errors {
+                report(&source, Diagnostic::from(err));
+            }
+            exit(1);
+        }
+    };
+
+    // Printed after checking, so that inferred types are shown
+    let checked = Checker::new().check(&mut tree);
+
+    if options.ast {
+        println!("{:?}", tree);
+    }
+
+    if let Err(errors) = checked {
+        for err in &errors {
+            report(&source, Diagnostic::from(err));
+        }
+        exit(1);
+    }
+
+    match options.interpreter().run(tree) {
+        Ok(value) => println!("{}", value),
+        Err(err) => {
+            report(&source, Diagnostic::from(&err));
+            exit(1);
+        }
+    }
+}
+
+pub fn interactive(options: &Options) {
+    let mut checker = Checker::new();
+    let mut interpreter = options.interpreter();
+
+    // Every input is appended to the history, so that the spans of the
+    // functions declared by previous inputs still point to their source
+    let mut history = String::new();
 
-pub fn interactive() {
     loop {
-        let mut lexer = Lexer::new();
-        let input = get_input();
+        let offset = history.len();
+        history.push_str(&get_input());
+        let source = SourceFile::new("<stdin>", history.clone());
+
+        let tokens = match lex(&source, offset, options) {
+            Some(tokens) => tokens,
+            None => continue,
+        };
+
+        let inputs = match Parser::new(tokens).parse_interactive() {
+            Ok(inputs) => inputs,
+            Err(errors) => {
+                for err in &errors {
+                    report(&source, Diagnostic::from(err));
+                }
+                continue;
+            }
+        };
+
+        for mut input in inputs {
+            let checked = checker.check_input(&mut input);
+
+            if options.ast {
+                println!("{:?}", input);
+            }
+
+            if let Err(errors) = checked {
+                for err in &errors {
+                    report(&source, Diagnostic::from(err));
+                }
+                break;
+            }
 
-        match lexer.lex(input) {
-            Ok(tokens) => {
+            // A global whose value cannot be evaluated is not declared
+            match interpreter.eval_input(input) {
+                Ok(Some(value)) => println!("{}", value),
+                Ok(None) => checker.declare_pending(),
+                Err(err) => {
+                    report(&source, Diagnostic::from(&err));
+                    break;
+                }
+            }
+        }
+    }
+}
+
+// Lexes the part of `source` starting at `offset`
+fn lex(source: &SourceFile, offset: usize, options: &Options) -> Option<Vec<Token>> {
+    let mut lexer = Lexer::new();
+
+    match lexer.lex(source.content[offset..].to_string()) {
+        Ok(tokens) => {
+            let tokens = tokens.iter()
+                .cloned()
+                .map(|mut token| {
+                    token.span = token.span.shifted(offset);
+                    token
+                })
+                .collect::<Vec<_>>();
+
+            if options.tokens {
                 println!("{:?}", tokens);
-                println!();
+            }
 
-                let mut parser = Parser::new(tokens.clone().to_vec());
-                parser.parse();
-            },
-            Err(err) => println!("{}", err),
+            Some(tokens)
+        }
+        Err(mut err) => {
+            err.span = err.span.shifted(offset);
+            report(source, Diagnostic::from(&err));
+            None
         }
     }
 }
 
+fn report(source: &SourceFile, diagnostic: Diagnostic) {
+    print!("{}", diagnostic.render(source));
+}
+
 fn get_input() -> String {
     let mut input = String::new();
 
@@ -28,6 +189,8 @@ fn get_input() -> String {
     stdout().flush().expect("Flush failed!");
 
     match stdin().read_line(&mut input) {
+        // End of input
+        Ok(0) => exit(0),
         Ok(_) => {
             if input.trim_end().ends_with('{') {
                 loop {
@@ -47,8 +210,8 @@ fn get_input() -> String {
                     input.push_str(&line);
                 }
             }
-            return input;
+            input
         }
         Err(err) => panic!("An error occured while trying to read input: {}", err)
     }
-}
\ No newline at end of file
+}
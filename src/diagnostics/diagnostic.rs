@@ -0,0 +1,186 @@
+use std::fmt;
+
+use crate::diagnostics::{source::SourceFile, span::Span};
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Severity {
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+    // The primary label is underlined with '^', the others with '-'
+    pub primary: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    pub labels: Vec<Label>,
+    pub notes: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn error(message: &str) -> Self {
+        Self {
+            severity: Severity::Error,
+            message: message.to_string(),
+            labels: Vec::new(),
+            notes: Vec::new(),
+        }
+    }
+
+    pub fn with_label(mut self, span: Span, message: &str) -> Self {
+        let primary = self.labels.is_empty();
+        self.labels.push(Label { span, message: message.to_string(), primary });
+        self
+    }
+
+    pub fn with_note(mut self, note: &str) -> Self {
+        self.notes.push(note.to_string());
+        self
+    }
+
+    // Renders the diagnostic the following way :
+    //
+    // error: Unexpected token
+    //  --> <stdin>:1:18
+    //   |
+    // 1 | fcn f(a :: Int64 b) -> Bool {
+    //   |                  ^ expected ',' or ')', got 'b'
+    //   |
+    //   = note: ...
+    pub fn render(&self, source: &SourceFile) -> String {
+        let mut output = format!("{}: {}\n", self.severity, self.message);
+
+        let mut labels = self.labels.iter()
+            .map(|label| (source.location(label.span.start), label))
+            .collect::<Vec<_>>();
+        labels.sort_by_key(|((line, column), _)| (*line, *column));
+
+        let gutter = labels.iter()
+            .map(|((line, _), _)| line.to_string().len())
+            .max()
+            .unwrap_or(0);
+        let padding = " ".repeat(gutter);
+
+        if let Some(((line, column), _)) = labels.iter().find(|(_, label)| label.primary) {
+            output.push_str(&format!("{}--> {}:{}:{}\n", padding, source.name, line, column));
+        }
+
+        if !labels.is_empty() {
+            output.push_str(&format!("{} |\n", padding));
+        }
+
+        let mut previous_line = None;
+        for ((line, column), label) in &labels {
+            let text = source.line(*line);
+
+            if previous_line != Some(*line) {
+                output.push_str(&format!("{:>width$} | {}\n", line, text, width = gutter));
+                previous_line = Some(*line);
+            }
+
+            // Reuse the tabs of the line so the markers stay aligned
+            let indent = text.chars()
+                .take(column - 1)
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect::<String>();
+
+            // Spans covering several lines are only underlined on their first one
+            let line_end = source.line_offset(*line) + text.len();
+            let end = label.span.end.min(line_end).max(label.span.start);
+            let width = source.content
+                .get(label.span.start..end)
+                .map_or(0, |s| s.chars().count())
+                .max(1);
+
+            let marker = if label.primary { "^" } else { "-" };
+            let underline = format!("{} | {}{} {}", padding, indent, marker.repeat(width), label.message);
+            output.push_str(underline.trim_end());
+            output.push('\n');
+        }
+
+        if !self.notes.is_empty() && !labels.is_empty() {
+            output.push_str(&format!("{} |\n", padding));
+        }
+
+        for note in &self.notes {
+            output.push_str(&format!("{} = note: {}\n", padding, note));
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(content: &str) -> SourceFile {
+        SourceFile::new("test.rnk", content.to_string())
+    }
+
+    #[test]
+    fn render_labels() {
+        let source = source("var x = 1\nfcn f(a :: Int64 b) -> Bool {\n");
+        let diagnostic = Diagnostic::error("Unexpected token")
+            .with_label(Span::new(27, 28), "expected ',' or ')', got 'b'")
+            .with_label(Span::new(15, 16), "list opened here");
+
+        assert_eq!(diagnostic.render(&source), "\
+error: Unexpected token
+ --> test.rnk:2:18
+  |
+2 | fcn f(a :: Int64 b) -> Bool {
+  |      - list opened here
+  |                  ^ expected ',' or ')', got 'b'
+");
+    }
+
+    #[test]
+    fn render_notes() {
+        let source = source("fcn f() -> Int64 {\n}");
+        let diagnostic = Diagnostic::error("Missing return")
+            .with_label(Span::new(0, 18), "")
+            .with_note("end the function with a 'return'");
+
+        assert_eq!(diagnostic.render(&source), "\
+error: Missing return
+ --> test.rnk:1:1
+  |
+1 | fcn f() -> Int64 {
+  | ^^^^^^^^^^^^^^^^^^
+  |
+  = note: end the function with a 'return'
+");
+
+        let diagnostic = Diagnostic::error("No main").with_note("add one");
+        assert_eq!(diagnostic.render(&source), "error: No main\n = note: add one\n");
+    }
+
+    #[test]
+    fn render_multiline_span_on_its_first_line() {
+        let source = source("let x = [\n\t1,\n]");
+        let diagnostic = Diagnostic::error("Mismatched types").with_label(Span::new(8, 15), "this is an array");
+
+        assert_eq!(diagnostic.render(&source), "\
+error: Mismatched types
+ --> test.rnk:1:9
+  |
+1 | let x = [
+  |         ^ this is an array
+");
+    }
+}
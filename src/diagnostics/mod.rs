@@ -0,0 +1,3 @@
+pub mod span;
+pub mod source;
+pub mod diagnostic;
@@ -0,0 +1,76 @@
+pub struct SourceFile {
+    pub name: String,
+    pub content: String,
+    // Byte offset of the first character of every line
+    line_starts: Vec<usize>,
+}
+
+impl SourceFile {
+    pub fn new(name: &str, content: String) -> Self {
+        let mut line_starts = vec![0];
+        line_starts.extend(content.match_indices('\n').map(|(i, _)| i + 1));
+
+        Self {
+            name: name.to_string(),
+            content,
+            line_starts,
+        }
+    }
+
+    // 1-based line and column (in characters) of a byte offset
+    pub fn location(&self, offset: usize) -> (usize, usize) {
+        let offset = offset.min(self.content.len());
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(next_line) => next_line - 1,
+        };
+
+        let column = self.content[self.line_starts[line]..offset].chars().count();
+        (line + 1, column + 1)
+    }
+
+    // Byte offset at which a 1-based line starts
+    pub fn line_offset(&self, line: usize) -> usize {
+        self.line_starts[line - 1]
+    }
+
+    // Content of a 1-based line, without its line break
+    pub fn line(&self, line: usize) -> &str {
+        let start = self.line_offset(line);
+        let end = match self.line_starts.get(line) {
+            Some(next_start) => next_start - 1,
+            None => self.content.len(),
+        };
+
+        self.content[start..end].trim_end_matches('\r')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locations() {
+        let source = SourceFile::new("test", "ab\nété x\r\n\nlast".to_string());
+
+        assert_eq!(source.location(0), (1, 1));
+        assert_eq!(source.location(2), (1, 3));
+        assert_eq!(source.location(3), (2, 1));
+        // Columns count characters, 'é' being two bytes
+        assert_eq!(source.location(9), (2, 5));
+        assert_eq!(source.location(13), (4, 1));
+        assert_eq!(source.location(100), (4, 5));
+    }
+
+    #[test]
+    fn lines() {
+        let source = SourceFile::new("test", "ab\nété x\r\n\nlast".to_string());
+
+        assert_eq!(source.line(1), "ab");
+        assert_eq!(source.line(2), "été x");
+        assert_eq!(source.line(3), "");
+        assert_eq!(source.line(4), "last");
+        assert_eq!(source.line_offset(4), 13);
+    }
+}
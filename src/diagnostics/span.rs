@@ -0,0 +1,28 @@
+// Byte range `start..end` of the source, `end` being exclusive
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    // Same span in a source where `offset` bytes were inserted before it
+    pub fn shifted(&self, offset: usize) -> Span {
+        Span {
+            start: self.start + offset,
+            end: self.end + offset,
+        }
+    }
+
+    // Smallest span covering both `self` and `other`
+    pub fn to(&self, other: Span) -> Span {
+        Span {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use std::fmt;
+
+use crate::diagnostics::{diagnostic::Diagnostic, span::Span};
+use crate::parser::types::Type;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum RuntimeErrorKind {
+    UndefinedVariable(String),
+    UndefinedFunction(String),
+    ArgumentCount { expected: usize, found: usize },
+    TypeMismatch { expected: Type, found: Type },
+    InvalidOperand { operator: String, found: Type },
+    LiteralOutOfRange(Type),
+    DivisionByZero,
+    Overflow,
+    ShiftOutOfRange,
+    MissingReturn(String),
+    MissingReturnValue(Type),
+    ReturnOutsideFunction,
+    BreakOutsideLoop,
+    StackOverflow,
+    MissingElse,
+    MissingValue,
+    StatementInExpression,
+    InvalidAssignTarget,
+    InvalidCast { from: Type, to: Type },
+    UndefinedType(String),
+    UndefinedField { found: Type, field: String },
+    MissingField { name: String, field: String },
+    UndefinedVariant { enum_name: String, variant: String },
+    NoMatchingArm,
+    NotIndexable(Type),
+    InvalidIndex(Type),
+    IndexOutOfBounds { index: i128, len: usize },
+    UnknownElementType,
+    ArrayTooLarge(usize),
+    UndefinedMethod { found: Type, method: String },
+    EmptyList,
+    RefutablePattern,
+    MissingImpl { trait_name: String, found: Type },
+}
+
+impl fmt::Display for RuntimeErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UndefinedVariable(name) => write!(f, "Undefined variable '{}'", name),
+            Self::UndefinedFunction(name) => write!(f, "Undefined function '{}'", name),
+            Self::ArgumentCount { expected, found } =>
+                write!(f, "Expected {} argument(s), got {}", expected, found),
+            Self::TypeMismatch { expected, found } =>
+                write!(f, "Mismatched types: expected {}, got {}", expected, found),
+            Self::InvalidOperand { operator, found } =>
+                write!(f, "Operator '{}' cannot be applied to {}", operator, found),
+            Self::LiteralOutOfRange(t) => write!(f, "Literal out of range for {}", t),
+            Self::DivisionByZero => write!(f, "Division by zero"),
+            Self::Overflow => write!(f, "Arithmetic overflow"),
+            Self::ShiftOutOfRange => write!(f, "Shift amount out of range"),
+            Self::MissingReturn(name) => write!(f, "Function '{}' ended without returning", name),
+            Self::MissingReturnValue(t) => write!(f, "'return' needs a value of type {}", t),
+            Self::ReturnOutsideFunction => write!(f, "'return' outside of a function"),
+            Self::BreakOutsideLoop => write!(f, "'break' or 'continue' outside of a loop"),
+            Self::StackOverflow => write!(f, "Maximum depth of calls and nested expressions exceeded"),
+            Self::MissingElse => write!(f, "'if' used as a value has no 'else' branch"),
+            Self::MissingValue => write!(f, "Block has no value"),
+            Self::StatementInExpression =>
+                write!(f, "'return', 'break' and 'continue' cannot be used inside an 'if' expression"),
+            Self::InvalidAssignTarget => write!(f, "Invalid left-hand side of assignment"),
+            Self::InvalidCast { from, to } => write!(f, "Cannot cast {} to {}", from, to),
+            Self::UndefinedType(name) => write!(f, "Undefined type '{}'", name),
+            Self::UndefinedField { found, field } => write!(f, "{} has no field '{}'", found, field),
+            Self::MissingField { name, field } => write!(f, "Missing field '{}' of {}", field, name),
+            Self::UndefinedVariant { enum_name, variant } =>
+                write!(f, "Enum '{}' has no variant '{}'", enum_name, variant),
+            Self::NoMatchingArm => write!(f, "No arm of the 'match' matches the value"),
+            Self::NotIndexable(t) => write!(f, "Cannot index into a value of type {}", t),
+            Self::InvalidIndex(t) => write!(f, "Indexes must be integers, got {}", t),
+            Self::IndexOutOfBounds { index, len } =>
+                write!(f, "Index {} is out of bounds for a length of {}", index, len),
+            Self::UnknownElementType => write!(f, "Cannot infer the type of an empty array"),
+            Self::ArrayTooLarge(length) => write!(f, "Not enough memory for an array of {} elements", length),
+            Self::UndefinedMethod { found, method } => write!(f, "{} has no method '{}'", found, method),
+            Self::EmptyList => write!(f, "Cannot pop from an empty list"),
+            Self::RefutablePattern => write!(f, "The value does not match the pattern of the declaration"),
+            Self::MissingImpl { trait_name, found } => write!(f, "{} does not implement trait '{}'", found, trait_name),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct RuntimeError {
+    // Boxed to keep the results of the interpreter small
+    pub kind: Box<RuntimeErrorKind>,
+    pub span: Span,
+}
+
+impl RuntimeError {
+    pub fn new(kind: RuntimeErrorKind, span: Span) -> Self {
+        Self { kind: Box::new(kind), span }
+    }
+}
+
+impl fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl From<&RuntimeError> for Diagnostic {
+    fn from(err: &RuntimeError) -> Self {
+        Diagnostic::error(&err.kind.to_string()).with_label(err.span, "")
+    }
+}
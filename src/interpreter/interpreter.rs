@@ -0,0 +1,1592 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::checker::generics::{bind, unify, Bindings};
+use crate::diagnostics::span::Span;
+use crate::interpreter::{
+    environment::Environment,
+    error::{RuntimeError, RuntimeErrorKind},
+    value::{OverflowMode, Value},
+};
+use crate::parser::{
+    tree::{
+        ParseTree, Item, Input, Stmt, Expr, VariableNode, BlockNode, FunctionNode,
+        FunctionCallNode, StatementNode, BinaryOpNode, IfNode, ElseBranch, LoopNode, LoopKind,
+        AssignNode, StructNode, StructLiteralNode, FieldNode, EnumNode, VariantCallNode, MatchNode,
+        Pattern, ArrayNode, ArrayElements, IndexNode, MethodCallNode, TupleNode, DestructureNode, ImplNode,
+    },
+    types::Type,
+    statements::Statement,
+    operators::{AssignOperator, BinaryOperator},
+};
+
+// Expressions and blocks nested deeper than this, calls included, are reported
+// instead of overflowing the native stack. The parser bounds the nesting of a
+// function body, but not how deep calls go
+const MAX_DEPTH: usize = 8192;
+
+// Native stack reaching `MAX_DEPTH` takes, even in a debug build
+pub const STACK_SIZE: usize = 256 * 1024 * 1024;
+
+// How the execution of a statement ended, 'break' and 'continue' carry
+// the label of the loop they target
+enum Flow {
+    Normal,
+    Return(Value),
+    Break(Option<String>, Span),
+    Continue(Option<String>, Span),
+}
+
+impl Flow {
+    // What a loop does after an iteration whose body ended with this flow:
+    // None to keep looping, or the flow the loop itself ends with
+    fn after_iteration(self, node: &LoopNode) -> Option<Flow> {
+        let targets = |label: &Option<String>| label.is_none() || *label == node.label;
+
+        match self {
+            Flow::Normal => None,
+            Flow::Break(label, _) if targets(&label) => Some(Flow::Normal),
+            Flow::Continue(label, _) if targets(&label) => None,
+            flow => Some(flow),
+        }
+    }
+}
+
+// Variable, or field or element of one, designated by an assignment target
+struct Place {
+    name: String,
+    span: Span,
+    path: Vec<Access>,
+}
+
+enum Access {
+    Field(String, Span),
+    // The index is evaluated once, when the place is resolved
+    Index(Value, Span),
+}
+
+struct Frame {
+    env: Environment,
+    // None outside of any function (i.e. at the top level of the REPL)
+    return_type: Option<Type>,
+    // Types given to the type parameters of the function being run
+    type_args: Bindings,
+}
+
+#[derive(Default)]
+pub struct Interpreter {
+    functions: HashMap<String, Rc<FunctionNode>>,
+    structs: HashMap<String, Rc<StructNode>>,
+    enums: HashMap<String, Rc<EnumNode>>,
+    // Traits only matter to the checker, methods are looked up in the impls
+    impls: Vec<Rc<ImplNode>>,
+    globals: Environment,
+    frames: Vec<Frame>,
+    overflow: OverflowMode,
+    // Nesting of the expression or block being run
+    depth: usize,
+}
+
+impl Interpreter {
+    pub fn new() -> Self {
+        Self {
+            functions: HashMap::new(),
+            structs: HashMap::new(),
+            enums: HashMap::new(),
+            impls: Vec::new(),
+            globals: Environment::new(),
+            frames: Vec::new(),
+            overflow: OverflowMode::Trap,
+            depth: 0,
+        }
+    }
+
+    // Integer overflows are errors unless the interpreter is set to wrap
+    pub fn with_overflow(mut self, overflow: OverflowMode) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    // Declares the functions, structs and enums of a program, then evaluates its global variables in order
+    pub fn load(&mut self, tree: ParseTree) -> Result<(), RuntimeError> {
+        let mut variables = Vec::new();
+
+        for item in tree.nodes {
+            match item {
+                Item::Function(node) => self.declare_function(node),
+                Item::Struct(node) => self.declare_struct(node),
+                Item::Enum(node) => self.declare_enum(node),
+                Item::Trait(_) => {}
+                Item::Impl(node) => self.declare_impl(node),
+                Item::Variable(node) => variables.push(node),
+            }
+        }
+
+        for variable in &variables {
+            self.declare_global(variable)?;
+        }
+
+        Ok(())
+    }
+
+    // Loads a program and returns the value of its `main` function
+    pub fn run(&mut self, tree: ParseTree) -> Result<Value, RuntimeError> {
+        self.load(tree)?;
+
+        let main = self.functions.get("main").cloned().ok_or_else(|| {
+            RuntimeError::new(RuntimeErrorKind::UndefinedFunction("main".to_string()), Span::default())
+        })?;
+
+        if !main.args.is_empty() {
+            return Err(RuntimeError::new(RuntimeErrorKind::ArgumentCount {
+                expected: main.args.len(),
+                found: 0,
+            }, main.span));
+        }
+
+        self.call(&main, Environment::new(), Bindings::new(), main.span)
+    }
+
+    // Executes an input of the REPL, returning its value if it is an expression
+    pub fn eval_input(&mut self, input: Input) -> Result<Option<Value>, RuntimeError> {
+        match input {
+            Input::Item(Item::Function(node)) => {
+                self.declare_function(node);
+                Ok(None)
+            }
+            Input::Item(Item::Struct(node)) => {
+                self.declare_struct(node);
+                Ok(None)
+            }
+            Input::Item(Item::Enum(node)) => {
+                self.declare_enum(node);
+                Ok(None)
+            }
+            Input::Item(Item::Trait(_)) => Ok(None),
+            Input::Item(Item::Impl(node)) => {
+                self.declare_impl(node);
+                Ok(None)
+            }
+            Input::Item(Item::Variable(node)) => {
+                self.declare_global(&node)?;
+                Ok(None)
+            }
+            // Run outside of any frame, so that its bindings are globals
+            Input::Stmt(Stmt::Destructure(node)) => {
+                self.exec_destructure(&node)?;
+                Ok(None)
+            }
+            Input::Stmt(stmt) => {
+                self.frames.push(Frame {
+                    env: Environment::new(),
+                    return_type: None,
+                    type_args: Bindings::new(),
+                });
+
+                let result = match &stmt {
+                    Stmt::Expr(expr) => self.eval_expr(expr, None).map(Some),
+                    _ => self.exec_stmt(&stmt).and_then(|flow| match flow {
+                        Flow::Break(_, span) | Flow::Continue(_, span) =>
+                            Err(RuntimeError::new(RuntimeErrorKind::BreakOutsideLoop, span)),
+                        _ => Ok(None),
+                    }),
+                };
+
+                self.frames.clear();
+                result
+            }
+        }
+    }
+
+    fn declare_function(&mut self, node: FunctionNode) {
+        self.functions.insert(node.name.clone(), Rc::new(node));
+    }
+
+    fn declare_struct(&mut self, node: StructNode) {
+        self.structs.insert(node.name.clone(), Rc::new(node));
+    }
+
+    fn declare_enum(&mut self, node: EnumNode) {
+        self.enums.insert(node.name.clone(), Rc::new(node));
+    }
+
+    // A new impl of a trait for the same type replaces the previous one
+    fn declare_impl(&mut self, node: ImplNode) {
+        self.impls.retain(|other| other.trait_name != node.trait_name || other.target != node.target);
+        self.impls.push(Rc::new(node));
+    }
+
+    fn declare_global(&mut self, node: &VariableNode) -> Result<(), RuntimeError> {
+        let value = self.eval_value(node)?;
+        self.globals.declare(&node.name, value);
+        Ok(())
+    }
+
+    fn env_mut(&mut self) -> &mut Environment {
+        match self.frames.last_mut() {
+            Some(frame) => &mut frame.env,
+            None => &mut self.globals,
+        }
+    }
+
+    // A type of the running function, with its type parameters replaced
+    fn resolve(&self, declared: &Type) -> Type {
+        match self.frames.last() {
+            Some(frame) => declared.substitute(&frame.type_args),
+            None => declared.clone(),
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<&Value> {
+        self.frames.last()
+            .and_then(|frame| frame.env.get(name))
+            .or_else(|| self.globals.get(name))
+    }
+
+    fn exec_stmt(&mut self, stmt: &Stmt) -> Result<Flow, RuntimeError> {
+        match stmt {
+            Stmt::Variable(node) => {
+                let value = self.eval_value(node)?;
+                self.env_mut().declare(&node.name, value);
+                Ok(Flow::Normal)
+            }
+            Stmt::Destructure(node) => self.exec_destructure(node),
+            Stmt::Block(node) => self.exec_block(node),
+            Stmt::If(node) => self.exec_if(node),
+            Stmt::Loop(node) => self.exec_loop(node),
+            Stmt::Match(node) => self.match_arm(node, Self::exec_block),
+            Stmt::Assign(node) => self.exec_assign(node),
+            Stmt::Statement(node) => self.exec_statement(node),
+            Stmt::Expr(expr) => {
+                self.eval_expr(expr, None)?;
+                Ok(Flow::Normal)
+            }
+        }
+    }
+
+    // The bindings of the pattern are declared in the current scope
+    fn exec_destructure(&mut self, node: &DestructureNode) -> Result<Flow, RuntimeError> {
+        let value = match &node.var_type {
+            Some(var_type) => self.eval_typed(&node.value, &self.resolve(var_type))?,
+            None => self.eval_expr(&node.value, None)?,
+        };
+
+        if !self.bind_pattern(&node.pattern, &value)? {
+            return Err(RuntimeError::new(RuntimeErrorKind::RefutablePattern, node.pattern.span()));
+        }
+        Ok(Flow::Normal)
+    }
+
+    // Runs `run` one level deeper
+    fn nested<T>(&mut self, span: Span, run: impl FnOnce(&mut Self) -> Result<T, RuntimeError>)
+                 -> Result<T, RuntimeError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(RuntimeError::new(RuntimeErrorKind::StackOverflow, span));
+        }
+
+        self.depth += 1;
+        let result = run(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn exec_block(&mut self, node: &BlockNode) -> Result<Flow, RuntimeError> {
+        self.nested(node.span, |interpreter| interpreter.exec_nodes(node))
+    }
+
+    fn exec_nodes(&mut self, node: &BlockNode) -> Result<Flow, RuntimeError> {
+        self.env_mut().push_scope();
+
+        for stmt in &node.nodes {
+            match self.exec_stmt(stmt) {
+                Ok(Flow::Normal) => {}
+                flow => {
+                    self.env_mut().pop_scope();
+                    return flow;
+                }
+            }
+        }
+
+        self.env_mut().pop_scope();
+        Ok(Flow::Normal)
+    }
+
+    fn exec_if(&mut self, node: &IfNode) -> Result<Flow, RuntimeError> {
+        if self.eval_condition(&node.condition)? {
+            return self.exec_block(&node.then_block);
+        }
+
+        match &node.else_branch {
+            Some(ElseBranch::If(else_if)) => self.exec_if(else_if),
+            Some(ElseBranch::Block(block)) => self.exec_block(block),
+            None => Ok(Flow::Normal),
+        }
+    }
+
+    fn exec_assign(&mut self, node: &AssignNode) -> Result<Flow, RuntimeError> {
+        let place = self.resolve_place(&node.target)?;
+        let current = self.place(&place)?.clone();
+
+        // A variable keeps the type of its declaration, except for the amount
+        // of a shift which can be of another integer type
+        let value = match node.operator {
+            AssignOperator::Compound(operator) if operator.is_shift() => self.eval_expr(&node.value, None)?,
+            _ => self.eval_typed(&node.value, &current.value_type())?,
+        };
+        let value = match node.operator {
+            AssignOperator::Assign => value,
+            AssignOperator::Compound(operator) => current.binary_op(operator, &value, self.overflow)
+                .map_err(|kind| RuntimeError::new(kind, node.span))?,
+        };
+
+        *self.place(&place)? = value;
+        Ok(Flow::Normal)
+    }
+
+    // Evaluates the indexes of an assignment target, a variable or a field
+    // or element of one, so it can then be read and written
+    fn resolve_place(&mut self, target: &Expr) -> Result<Place, RuntimeError> {
+        match target {
+            Expr::VariableCall(node) => Ok(Place { name: node.name.clone(), span: node.span, path: Vec::new() }),
+            Expr::Field(node) => {
+                let mut place = self.resolve_place(&node.expr)?;
+                place.path.push(Access::Field(node.field.clone(), node.span));
+                Ok(place)
+            }
+            Expr::Index(node) => {
+                let mut place = self.resolve_place(&node.expr)?;
+                let index = self.eval_expr(&node.index, None)?;
+                place.path.push(Access::Index(index, node.index.span()));
+                Ok(place)
+            }
+            target => Err(RuntimeError::new(RuntimeErrorKind::InvalidAssignTarget, target.span())),
+        }
+    }
+
+    fn place(&mut self, place: &Place) -> Result<&mut Value, RuntimeError> {
+        let env = match self.frames.last_mut() {
+            Some(frame) if frame.env.get(&place.name).is_some() => &mut frame.env,
+            _ => &mut self.globals,
+        };
+        let mut value = env.get_mut(&place.name).ok_or_else(|| {
+            RuntimeError::new(RuntimeErrorKind::UndefinedVariable(place.name.clone()), place.span)
+        })?;
+
+        for access in &place.path {
+            value = match access {
+                Access::Field(field, span) => {
+                    let found = value.value_type();
+                    value.field_mut(field).ok_or_else(|| RuntimeError::new(
+                        RuntimeErrorKind::UndefinedField { found, field: field.clone() }, *span))?
+                }
+                Access::Index(index, span) =>
+                    value.element_mut(index).map_err(|kind| RuntimeError::new(kind, *span))?,
+            };
+        }
+
+        Ok(value)
+    }
+
+    fn exec_loop(&mut self, node: &LoopNode) -> Result<Flow, RuntimeError> {
+        match &node.kind {
+            LoopKind::Infinite => loop {
+                if let Some(flow) = self.exec_block(&node.block)?.after_iteration(node) {
+                    return Ok(flow);
+                }
+            },
+            LoopKind::While(condition) => {
+                while self.eval_condition(condition)? {
+                    if let Some(flow) = self.exec_block(&node.block)?.after_iteration(node) {
+                        return Ok(flow);
+                    }
+                }
+                Ok(Flow::Normal)
+            }
+            LoopKind::For { var_name, start, end } => self.exec_for(node, var_name, start, end),
+        }
+    }
+
+    // The bounds are evaluated once, before the first iteration
+    fn exec_for(&mut self, node: &LoopNode, var_name: &str, start: &Expr, end: &Expr)
+                -> Result<Flow, RuntimeError> {
+        let (mut current, bound) = self.eval_operands(start, end, None)?;
+        let one = Value::from_integer(false, 1, Some(&current.value_type()))
+            .map_err(|kind| RuntimeError::new(kind, start.span()))?;
+
+        while current.binary_op(BinaryOperator::Less, &bound, self.overflow)
+            .map_err(|kind| RuntimeError::new(kind, end.span()))? == Value::Bool(true) {
+            self.env_mut().push_scope();
+            self.env_mut().declare(var_name, current.clone());
+            let flow = self.exec_block(&node.block);
+            self.env_mut().pop_scope();
+
+            if let Some(flow) = flow?.after_iteration(node) {
+                return Ok(flow);
+            }
+
+            // Cannot overflow as `current` is below `bound`
+            current = current.binary_op(BinaryOperator::Add, &one, self.overflow)
+                .map_err(|kind| RuntimeError::new(kind, start.span()))?;
+        }
+
+        Ok(Flow::Normal)
+    }
+
+    fn eval_condition(&mut self, expr: &Expr) -> Result<bool, RuntimeError> {
+        match self.eval_typed(expr, &Type::Bool)? {
+            Value::Bool(value) => Ok(value),
+            value => Err(RuntimeError::new(RuntimeErrorKind::TypeMismatch {
+                expected: Type::Bool,
+                found: value.value_type(),
+            }, expr.span())),
+        }
+    }
+
+    fn eval_if(&mut self, node: &IfNode, expected: Option<&Type>) -> Result<Value, RuntimeError> {
+        if self.eval_condition(&node.condition)? {
+            return self.eval_block(&node.then_block, expected);
+        }
+
+        match &node.else_branch {
+            Some(ElseBranch::If(else_if)) => self.eval_if(else_if, expected),
+            Some(ElseBranch::Block(block)) => self.eval_block(block, expected),
+            None => Err(RuntimeError::new(RuntimeErrorKind::MissingElse, node.span)),
+        }
+    }
+
+    // Executes a block and returns the value of the expression ending it
+    fn eval_block(&mut self, node: &BlockNode, expected: Option<&Type>) -> Result<Value, RuntimeError> {
+        self.env_mut().push_scope();
+        let value = self.eval_block_scoped(node, expected);
+        self.env_mut().pop_scope();
+        value
+    }
+
+    fn eval_block_scoped(&mut self, node: &BlockNode, expected: Option<&Type>)
+                         -> Result<Value, RuntimeError> {
+        let (tail, stmts) = match node.nodes.split_last() {
+            Some((tail, stmts)) if tail.is_tail_value() => (tail, stmts),
+            _ => return Err(RuntimeError::new(RuntimeErrorKind::MissingValue, node.span)),
+        };
+
+        for stmt in stmts {
+            match self.exec_stmt(stmt)? {
+                Flow::Normal => {}
+                _ => return Err(RuntimeError::new(
+                    RuntimeErrorKind::StatementInExpression, stmt.span())),
+            }
+        }
+
+        match tail {
+            Stmt::If(tail) => self.eval_if(tail, expected),
+            Stmt::Match(tail) => self.match_arm(tail, |this, body| this.eval_block(body, expected)),
+            Stmt::Expr(tail) => self.eval_expr(tail, expected),
+            _ => Err(RuntimeError::new(RuntimeErrorKind::MissingValue, node.span)),
+        }
+    }
+
+    // Runs `run` on the body of the first arm whose pattern matches the
+    // value, with the bindings of the pattern in scope
+    fn match_arm<T>(&mut self, node: &MatchNode,
+                    run: impl FnOnce(&mut Self, &BlockNode) -> Result<T, RuntimeError>)
+                    -> Result<T, RuntimeError> {
+        let value = self.eval_expr(&node.value, None)?;
+
+        for arm in &node.arms {
+            self.env_mut().push_scope();
+
+            match self.bind_pattern(&arm.pattern, &value) {
+                Ok(true) => {
+                    let result = run(self, &arm.body);
+                    self.env_mut().pop_scope();
+                    return result;
+                }
+                Ok(false) => self.env_mut().pop_scope(),
+                Err(err) => {
+                    self.env_mut().pop_scope();
+                    return Err(err);
+                }
+            }
+        }
+
+        Err(RuntimeError::new(RuntimeErrorKind::NoMatchingArm, node.value.span()))
+    }
+
+    // Whether the pattern matches the value, declaring its bindings if it does
+    fn bind_pattern(&mut self, pattern: &Pattern, value: &Value) -> Result<bool, RuntimeError> {
+        match (pattern, value) {
+            (Pattern::Wildcard(_), _) => Ok(true),
+            (Pattern::Binding(name, _), _) => {
+                self.env_mut().declare(name, value.clone());
+                Ok(true)
+            }
+            (Pattern::Literal(expr), _) => Ok(self.eval_expr(expr, Some(&value.value_type()))? == *value),
+            (Pattern::Variant { variant, fields, .. }, Value::Enum { variant: found, payload, .. })
+                if variant == found => self.bind_all(fields, payload),
+            (Pattern::Tuple(patterns, _), Value::Tuple(elements)) => self.bind_all(patterns, elements),
+            _ => Ok(false),
+        }
+    }
+
+    fn bind_all(&mut self, patterns: &[Pattern], values: &[Value]) -> Result<bool, RuntimeError> {
+        for (pattern, value) in patterns.iter().zip(values) {
+            if !self.bind_pattern(pattern, value)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn exec_statement(&mut self, node: &StatementNode) -> Result<Flow, RuntimeError> {
+        match node.stat_type {
+            Statement::Return => {
+                let return_type = self.frames.last()
+                    .and_then(|frame| frame.return_type.clone())
+                    .ok_or_else(|| {
+                        RuntimeError::new(RuntimeErrorKind::ReturnOutsideFunction, node.span)
+                    })?;
+
+                match &node.value {
+                    Some(expr) => Ok(Flow::Return(self.eval_typed(expr, &return_type)?)),
+                    None => Err(RuntimeError::new(
+                        RuntimeErrorKind::MissingReturnValue(return_type), node.span)),
+                }
+            }
+            Statement::Break => Ok(Flow::Break(node.label.clone(), node.span)),
+            Statement::Continue => Ok(Flow::Continue(node.label.clone(), node.span)),
+        }
+    }
+
+    // Evaluates the value of a declaration, which gives the type of the
+    // variable if the checker did not infer it
+    fn eval_value(&mut self, node: &VariableNode) -> Result<Value, RuntimeError> {
+        match &node.var_type {
+            Some(var_type) => self.eval_typed(&node.value, &self.resolve(var_type)),
+            None => self.eval_expr(&node.value, None),
+        }
+    }
+
+    // Evaluates an expression which must produce a value of type `expected`
+    fn eval_typed(&mut self, expr: &Expr, expected: &Type) -> Result<Value, RuntimeError> {
+        let value = self.eval_expr(expr, Some(expected))?;
+
+        if value.value_type() != *expected {
+            return Err(RuntimeError::new(RuntimeErrorKind::TypeMismatch {
+                expected: expected.clone(),
+                found: value.value_type(),
+            }, expr.span()));
+        }
+
+        Ok(value)
+    }
+
+    // `expected` is the type the context expects, it gives its type to number literals
+    fn eval_expr(&mut self, expr: &Expr, expected: Option<&Type>) -> Result<Value, RuntimeError> {
+        self.nested(expr.span(), |interpreter| interpreter.eval_unnested(expr, expected))
+    }
+
+    fn eval_unnested(&mut self, expr: &Expr, expected: Option<&Type>) -> Result<Value, RuntimeError> {
+        match expr {
+            Expr::Bool(node) => Ok(Value::Bool(node.value)),
+            // The checker records the type of the literal, the context gives
+            // it to an unchecked one
+            Expr::Number(node) => Value::from_number(node.value, node.number_type.as_ref().or(expected))
+                .map_err(|kind| RuntimeError::new(kind, node.span)),
+            Expr::String(node) => Ok(Value::String(node.value.clone())),
+            Expr::Char(node) => Ok(Value::Char(node.value)),
+            Expr::VariableCall(node) => self.lookup(&node.name).cloned().ok_or_else(|| {
+                RuntimeError::new(RuntimeErrorKind::UndefinedVariable(node.name.clone()), node.span)
+            }),
+            Expr::FunctionCall(node) => self.call_function(node),
+            Expr::BinaryOp(node) => self.eval_binary_op(node, expected),
+            Expr::UnaryOp(node) => {
+                let operand = self.eval_expr(&node.operand, expected)?;
+                operand.unary_op(node.operator, self.overflow)
+                    .map_err(|kind| RuntimeError::new(kind, node.span))
+            }
+            Expr::Cast(node) => {
+                let value = self.eval_expr(&node.expr, None)?;
+                value.cast(&self.resolve(&node.target)).map_err(|kind| RuntimeError::new(kind, node.span))
+            }
+            Expr::If(node) => self.eval_if(node, expected),
+            Expr::StructLiteral(node) => self.eval_struct_literal(node),
+            Expr::Field(node) => self.eval_field(node),
+            Expr::VariantCall(node) => self.eval_variant_call(node),
+            Expr::Match(node) => self.match_arm(node, |this, body| this.eval_block(body, expected)),
+            Expr::Array(node) => self.eval_array(node, expected),
+            Expr::Index(node) => self.eval_index(node),
+            Expr::MethodCall(node) => self.call_method(node),
+            Expr::Tuple(node) => self.eval_tuple(node, expected),
+        }
+    }
+
+    fn eval_tuple(&mut self, node: &TupleNode, expected: Option<&Type>) -> Result<Value, RuntimeError> {
+        let hints = match expected {
+            Some(Type::Tuple(types)) if types.len() == node.elements.len() => Some(types),
+            _ => None,
+        };
+
+        let mut elements = Vec::new();
+        for (i, element) in node.elements.iter().enumerate() {
+            elements.push(self.eval_expr(element, hints.map(|types| &types[i]))?);
+        }
+        Ok(Value::Tuple(elements))
+    }
+
+    // The checker records the type of the literal, the elements of an
+    // unchecked one take the type of the first of them
+    fn eval_array(&mut self, node: &ArrayNode, expected: Option<&Type>) -> Result<Value, RuntimeError> {
+        let array_type = node.array_type.as_ref().map(|array_type| self.resolve(array_type));
+        let array_type = array_type.as_ref().or(expected);
+        let mut element_type = array_type.and_then(Type::element_type).cloned();
+
+        let elements = match &node.elements {
+            ArrayElements::Values(values) => {
+                let mut elements = Vec::new();
+                for value in values {
+                    let value = match &element_type {
+                        Some(element_type) => self.eval_typed(value, element_type)?,
+                        None => self.eval_expr(value, None)?,
+                    };
+                    element_type.get_or_insert_with(|| value.value_type());
+                    elements.push(value);
+                }
+                elements
+            }
+            ArrayElements::Repeat { value, length } => {
+                let value = match &element_type {
+                    Some(element_type) => self.eval_typed(value, element_type)?,
+                    None => self.eval_expr(value, None)?,
+                };
+                element_type.get_or_insert_with(|| value.value_type());
+
+                let mut elements = Vec::new();
+                elements.try_reserve_exact(*length).map_err(|_| {
+                    RuntimeError::new(RuntimeErrorKind::ArrayTooLarge(*length), node.span)
+                })?;
+                elements.resize(*length, value);
+                elements
+            }
+        };
+
+        let element_type = element_type.ok_or_else(|| {
+            RuntimeError::new(RuntimeErrorKind::UnknownElementType, node.span)
+        })?;
+
+        Ok(match array_type {
+            Some(Type::List(_)) => Value::List { element_type, elements },
+            _ => Value::Array { element_type, elements },
+        })
+    }
+
+    // Elements of variables are read in place rather than copying the whole collection
+    fn eval_index(&mut self, node: &IndexNode) -> Result<Value, RuntimeError> {
+        if is_place(&node.expr) {
+            let place = self.resolve_place(&node.expr)?;
+            let index = self.eval_expr(&node.index, None)?;
+            return self.place(&place)?.element(&index).cloned()
+                .map_err(|kind| RuntimeError::new(kind, node.index.span()));
+        }
+
+        let value = self.eval_expr(&node.expr, None)?;
+        let index = self.eval_expr(&node.index, None)?;
+        value.element(&index).cloned().map_err(|kind| RuntimeError::new(kind, node.index.span()))
+    }
+
+    // A method is called on the receiver in place when it is a variable, or a
+    // field or element of one, so that it can modify it
+    fn call_method(&mut self, node: &MethodCallNode) -> Result<Value, RuntimeError> {
+        if let Some(trait_name) = &node.trait_name {
+            let receiver = self.eval_expr(&node.receiver, None)?;
+            return self.call_trait_method(node, trait_name, receiver);
+        }
+
+        if is_place(&node.receiver) {
+            let place = self.resolve_place(&node.receiver)?;
+            let receiver_type = self.place(&place)?.value_type();
+            let args = self.eval_method_args(node, &receiver_type)?;
+            return self.place(&place)?.call_method(&node.method, args)
+                .map_err(|kind| RuntimeError::new(kind, node.span));
+        }
+
+        let mut receiver = self.eval_expr(&node.receiver, None)?;
+        let args = self.eval_method_args(node, &receiver.value_type())?;
+        receiver.call_method(&node.method, args).map_err(|kind| RuntimeError::new(kind, node.span))
+    }
+
+    // Runs the method of the impl of the trait matching the type of the
+    // receiver, which is given to it as `self`
+    fn call_trait_method(&mut self, node: &MethodCallNode, trait_name: &str, receiver: Value)
+                         -> Result<Value, RuntimeError> {
+        let found = receiver.value_type();
+
+        let mut type_args = Bindings::new();
+        let implementation = self.impls.iter()
+            .find(|implementation| {
+                type_args.clear();
+                implementation.trait_name == trait_name && unify(&implementation.target, &found, &mut type_args)
+            })
+            .cloned()
+            .ok_or_else(|| RuntimeError::new(RuntimeErrorKind::MissingImpl {
+                trait_name: trait_name.to_string(),
+                found: found.clone(),
+            }, node.span))?;
+
+        let method = implementation.methods.iter()
+            .find(|method| method.name == node.method)
+            .ok_or_else(|| RuntimeError::new(RuntimeErrorKind::UndefinedMethod {
+                found,
+                method: node.method.clone(),
+            }, node.span))?;
+
+        if node.args.len() + 1 != method.args.len() {
+            return Err(RuntimeError::new(RuntimeErrorKind::ArgumentCount {
+                expected: method.args.len().saturating_sub(1),
+                found: node.args.len(),
+            }, node.span));
+        }
+
+        let mut env = Environment::new();
+        env.declare(&method.args[0].name, receiver);
+        for (arg, param) in node.args.iter().zip(&method.args[1..]) {
+            let value = self.eval_typed(arg, &param.var_type.substitute(&type_args))?;
+            env.declare(&param.name, value);
+        }
+
+        self.call(method, env, type_args, node.span)
+    }
+
+    // The arguments of the built-in methods are elements of the receiver
+    fn eval_method_args(&mut self, node: &MethodCallNode, receiver_type: &Type) -> Result<Vec<Value>, RuntimeError> {
+        let element_type = receiver_type.element_type();
+
+        let mut args = Vec::new();
+        for arg in &node.args {
+            args.push(match element_type {
+                Some(element_type) => self.eval_typed(arg, element_type)?,
+                None => self.eval_expr(arg, None)?,
+            });
+        }
+        Ok(args)
+    }
+
+    fn eval_variant_call(&mut self, node: &VariantCallNode) -> Result<Value, RuntimeError> {
+        let declaration = self.enums.get(&node.enum_name).cloned().ok_or_else(|| {
+            RuntimeError::new(RuntimeErrorKind::UndefinedType(node.enum_name.clone()), node.span)
+        })?;
+
+        let variant = declaration.variants.iter()
+            .find(|variant| variant.name == node.variant)
+            .ok_or_else(|| RuntimeError::new(RuntimeErrorKind::UndefinedVariant {
+                enum_name: node.enum_name.clone(),
+                variant: node.variant.clone(),
+            }, node.span))?;
+
+        if node.args.len() != variant.payload.len() {
+            return Err(RuntimeError::new(RuntimeErrorKind::ArgumentCount {
+                expected: variant.payload.len(),
+                found: node.args.len(),
+            }, node.span));
+        }
+
+        let type_args = self.resolve_all(&node.type_args);
+        let bindings = bind(&declaration.type_params, &type_args);
+
+        let mut payload = Vec::new();
+        for (arg, arg_type) in node.args.iter().zip(&variant.payload) {
+            payload.push(self.eval_typed(arg, &arg_type.substitute(&bindings))?);
+        }
+
+        Ok(Value::Enum { name: node.enum_name.clone(), type_args, variant: node.variant.clone(), payload })
+    }
+
+    // Fields are evaluated in the order they are written, and stored in
+    // the order of the declaration
+    fn eval_struct_literal(&mut self, node: &StructLiteralNode) -> Result<Value, RuntimeError> {
+        let declaration = self.structs.get(&node.name).cloned().ok_or_else(|| {
+            RuntimeError::new(RuntimeErrorKind::UndefinedType(node.name.clone()), node.span)
+        })?;
+
+        let type_args = self.resolve_all(&node.type_args);
+        let bindings = bind(&declaration.type_params, &type_args);
+
+        let mut given = Vec::new();
+        for field in &node.fields {
+            let value = match declaration.fields.iter().find(|declared| declared.name == field.name) {
+                Some(declared) => self.eval_typed(&field.value, &declared.field_type.substitute(&bindings))?,
+                None => return Err(RuntimeError::new(RuntimeErrorKind::UndefinedField {
+                    found: Type::Named(node.name.clone(), type_args),
+                    field: field.name.clone(),
+                }, field.span)),
+            };
+            given.push((field.name.clone(), value));
+        }
+
+        let mut fields = Vec::new();
+        for declared in &declaration.fields {
+            let index = given.iter().position(|(name, _)| *name == declared.name).ok_or_else(|| {
+                RuntimeError::new(RuntimeErrorKind::MissingField {
+                    name: node.name.clone(),
+                    field: declared.name.clone(),
+                }, node.span)
+            })?;
+            fields.push(given.swap_remove(index));
+        }
+
+        Ok(Value::Struct { name: node.name.clone(), type_args, fields })
+    }
+
+    fn eval_field(&mut self, node: &FieldNode) -> Result<Value, RuntimeError> {
+        let value = self.eval_expr(&node.expr, None)?;
+
+        value.field(&node.field).cloned().ok_or_else(|| RuntimeError::new(RuntimeErrorKind::UndefinedField {
+            found: value.value_type(),
+            field: node.field.clone(),
+        }, node.span))
+    }
+
+    fn eval_binary_op(&mut self, node: &BinaryOpNode, expected: Option<&Type>)
+                      -> Result<Value, RuntimeError> {
+        // The result of a comparison says nothing about the type of its operands
+        let expected = if node.operator.is_comparison() { None } else { expected };
+
+        // The right operand of a logical operator is skipped if the left one decides the result
+        if node.operator.is_logical() {
+            let left = self.eval_condition(&node.left)?;
+            let result = match node.operator {
+                BinaryOperator::And => left && self.eval_condition(&node.right)?,
+                _ => left || self.eval_condition(&node.right)?,
+            };
+            return Ok(Value::Bool(result));
+        }
+
+        // Like in the checker, the amount of a shift gets no type from the shifted value
+        let (left, right) = if node.operator.is_shift() {
+            (self.eval_expr(&node.left, expected)?, self.eval_expr(&node.right, None)?)
+        } else {
+            self.eval_operands(&node.left, &node.right, expected)?
+        };
+        left.binary_op(node.operator, &right, self.overflow).map_err(|kind| RuntimeError::new(kind, node.span))
+    }
+
+    // Evaluates two expressions expected to be of the same type, a literal
+    // operand takes the type of the other one
+    fn eval_operands(&mut self, left: &Expr, right: &Expr, expected: Option<&Type>)
+                     -> Result<(Value, Value), RuntimeError> {
+        if left.is_untyped() && !right.is_untyped() {
+            let right_value = self.eval_expr(right, expected)?;
+            let left_value = self.eval_expr(left, Some(&right_value.value_type()))?;
+            Ok((left_value, right_value))
+        } else {
+            let left_value = self.eval_expr(left, expected)?;
+            let right_value = self.eval_expr(right, Some(&left_value.value_type()))?;
+            Ok((left_value, right_value))
+        }
+    }
+
+    fn call_function(&mut self, node: &FunctionCallNode) -> Result<Value, RuntimeError> {
+        let function = self.functions.get(&node.name).cloned().ok_or_else(|| {
+            RuntimeError::new(RuntimeErrorKind::UndefinedFunction(node.name.clone()), node.span)
+        })?;
+
+        if node.args.len() != function.args.len() {
+            return Err(RuntimeError::new(RuntimeErrorKind::ArgumentCount {
+                expected: function.args.len(),
+                found: node.args.len(),
+            }, node.span));
+        }
+
+        // The type arguments recorded by the checker can refer to the type
+        // parameters of the caller
+        let type_args = bind(&function.type_params, &self.resolve_all(&node.type_args));
+
+        let mut env = Environment::new();
+        for (arg, param) in node.args.iter().zip(&function.args) {
+            let value = self.eval_typed(arg, &param.var_type.substitute(&type_args))?;
+            env.declare(&param.name, value);
+        }
+
+        self.call(&function, env, type_args, node.span)
+    }
+
+    fn resolve_all(&self, types: &[Type]) -> Vec<Type> {
+        types.iter().map(|declared| self.resolve(declared)).collect()
+    }
+
+    // Runs the body of a function with its arguments already bound in `env`
+    fn call(&mut self, function: &FunctionNode, env: Environment, type_args: Bindings, span: Span)
+            -> Result<Value, RuntimeError> {
+        let return_type = function.return_type.substitute(&type_args);
+        self.frames.push(Frame { env, return_type: Some(return_type), type_args });
+        let flow = self.exec_block(&function.block);
+        self.frames.pop();
+
+        match flow? {
+            Flow::Return(value) => Ok(value),
+            Flow::Normal => Err(RuntimeError::new(
+                RuntimeErrorKind::MissingReturn(function.name.clone()), span)),
+            Flow::Break(_, span) | Flow::Continue(_, span) =>
+                Err(RuntimeError::new(RuntimeErrorKind::BreakOutsideLoop, span)),
+        }
+    }
+}
+
+// Whether the expression designates a variable, or a field or element of one
+fn is_place(expr: &Expr) -> bool {
+    match expr {
+        Expr::VariableCall(_) => true,
+        Expr::Field(node) => is_place(&node.expr),
+        Expr::Index(node) => is_place(&node.expr),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checker::checker::Checker;
+    use crate::lexer::lexer::Lexer;
+    use crate::parser::parser::Parser;
+
+    // Checks and runs a program, returning the value of its `main` function
+    fn run_with(source: &str, overflow: OverflowMode) -> Result<Value, RuntimeErrorKind> {
+        let tokens = Lexer::new().lex(source.to_string()).unwrap().clone();
+        let mut tree = Parser::new(tokens).parse().unwrap();
+        Checker::new().check(&mut tree).unwrap();
+
+        Interpreter::new().with_overflow(overflow).run(tree).map_err(|err| *err.kind)
+    }
+
+    fn run(source: &str) -> Result<Value, RuntimeErrorKind> {
+        run_with(source, OverflowMode::Trap)
+    }
+
+    #[test]
+    fn run_calls_main() {
+        assert_eq!(run("
+            fcn main() -> String {
+                return \"done\"
+            }
+        "), Ok(Value::String("done".to_string())));
+
+        // Without the checker, a missing `main` is still reported
+        let tokens = Lexer::new().lex("var x = 1".to_string()).unwrap().clone();
+        let tree = Parser::new(tokens).parse().unwrap();
+        let err = Interpreter::new().run(tree).unwrap_err();
+        assert_eq!(*err.kind, RuntimeErrorKind::UndefinedFunction("main".to_string()));
+    }
+
+    // Runs a program without checking it first, for the errors the checker would report
+    fn run_unchecked(source: &str) -> Result<Value, RuntimeErrorKind> {
+        let tokens = Lexer::new().lex(source.to_string()).unwrap().clone();
+        let tree = Parser::new(tokens).parse().unwrap();
+
+        Interpreter::new().run(tree).map_err(|err| *err.kind)
+    }
+
+    #[test]
+    fn globals_and_calls() {
+        assert_eq!(run("
+            var a = 2
+            var b = a * 3
+            fcn f(x :: Int64, y :: Int64) -> Int64 {
+                return x - y
+            }
+            fcn main() -> Int64 {
+                return f(b, a) + f(a, b)
+            }
+        "), Ok(Value::Int64(0)));
+    }
+
+    #[test]
+    fn unchecked_errors() {
+        assert_eq!(run_unchecked("
+            fcn main() -> Int64 {
+                return x
+            }
+        "), Err(RuntimeErrorKind::UndefinedVariable("x".to_string())));
+
+        assert_eq!(run_unchecked("
+            fcn main() -> Int64 {
+                return f(1)
+            }
+            fcn f() -> Int64 {
+                return 1
+            }
+        "), Err(RuntimeErrorKind::ArgumentCount { expected: 0, found: 1 }));
+
+        assert_eq!(run_unchecked("
+            fcn main() -> Int64 {
+                var x = 1
+            }
+        "), Err(RuntimeErrorKind::MissingReturn("main".to_string())));
+    }
+
+    #[test]
+    fn number_literals() {
+        assert_eq!(run("
+            fcn main() -> Float64 {
+                var f :: Float64 = 1
+                return f + 2.5e1 + -0.5
+            }
+        "), Ok(Value::Float64(25.5)));
+
+        assert_eq!(run("
+            fcn main() -> UInt8 {
+                return 0xF0 + 0b1010 + 0o5
+            }
+        "), Ok(Value::UInt8(255)));
+
+        assert_eq!(run("
+            fcn main() -> Int128 {
+                return -170_141_183_460_469_231_731_687_303_715_884_105_728
+            }
+        "), Ok(Value::Int128(i128::MIN)));
+    }
+
+    #[test]
+    fn conditions() {
+        let sign = |x: i64| run(&format!("
+            fcn sign(x :: Int64) -> String {{
+                if x > 0 {{
+                    return \"positive\"
+                }} else if x < 0 {{
+                    return \"negative\"
+                }}
+                return if x == 0 {{ \"zero\" }} else {{ \"unreachable\" }}
+            }}
+            fcn main() -> String {{
+                return sign({})
+            }}
+        ", x));
+
+        assert_eq!(sign(5), Ok(Value::String("positive".to_string())));
+        assert_eq!(sign(-5), Ok(Value::String("negative".to_string())));
+        assert_eq!(sign(0), Ok(Value::String("zero".to_string())));
+    }
+
+    #[test]
+    fn loops() {
+        assert_eq!(run("
+            fcn main() -> Int64 {
+                var total = 0
+                var i = 0
+                while i < 5 {
+                    i += 1
+                    if i == 2 {
+                        continue
+                    }
+                    total += i
+                }
+                loop {
+                    total *= 10
+                    break
+                }
+                for j in -2..2 {
+                    total += j
+                }
+                return total
+            }
+        "), Ok(Value::Int64(128)));
+    }
+
+    #[test]
+    fn labeled_loops() {
+        assert_eq!(run("
+            fcn main() -> Int64 {
+                var pairs = 0
+                'outer: for i in 0..10 {
+                    for j in 0..10 {
+                        if j > i {
+                            continue 'outer
+                        }
+                        if i * j > 20 {
+                            break 'outer
+                        }
+                        pairs += 1
+                    }
+                }
+                return pairs
+            }
+        "), Ok(Value::Int64(20)));
+    }
+
+    #[test]
+    fn assignments() {
+        assert_eq!(run("
+            fcn main() -> Int64 {
+                var x = 7
+                x += 3
+                x *= 4
+                x -= 5
+                x /= 7
+                x %= 4
+                x <<= 3
+                x |= 1
+                x ^= 3
+                x &= 14
+                x >>= 1
+                return x
+            }
+        "), Ok(Value::Int64(5)));
+    }
+
+    #[test]
+    fn short_circuit() {
+        // The right operand would divide by zero if it was evaluated
+        assert_eq!(run("
+            fcn fail() -> Bool {
+                return 1 / 0 == 0
+            }
+            fcn main() -> Bool {
+                return (False and fail()) || (True or fail())
+            }
+        "), Ok(Value::Bool(true)));
+
+        assert_eq!(run("
+            fcn fail() -> Bool {
+                return 1 / 0 == 0
+            }
+            fcn main() -> Bool {
+                return True && fail()
+            }
+        "), Err(RuntimeErrorKind::DivisionByZero));
+    }
+
+    #[test]
+    fn equality() {
+        assert_eq!(run("
+            fcn main() -> Bool {
+                return 1 == 1 and 'a' != 'b' and \"ab\" < \"b\" and not (2.5 <= 1.0) and True != False
+            }
+        "), Ok(Value::Bool(true)));
+    }
+
+    #[test]
+    fn unsigned_integers() {
+        assert_eq!(run("
+            fcn main() -> UInt128 {
+                var x :: UInt128 = 1 << 127
+                return x / 2 + x
+            }
+        "), Ok(Value::UInt128(3 << 126)));
+
+        let underflow = "
+            fcn main() -> UInt32 {
+                var x :: UInt32 = 0
+                return x - 1
+            }
+        ";
+        assert_eq!(run(underflow), Err(RuntimeErrorKind::Overflow));
+        assert_eq!(run_with(underflow, OverflowMode::Wrap), Ok(Value::UInt32(u32::MAX)));
+    }
+
+    #[test]
+    fn casts() {
+        assert_eq!(run("
+            fcn main() -> (UInt8, UInt8, Int32, Int32, Int64, Char, Int16, Float32) {
+                let nan = 0.0 / 0.0
+                let c :: UInt8 = 97
+                let code = 'A' as Int16 + True as Int16
+                return (-1 as UInt8, 300 as UInt8, 1e10 as Int32, -2.9 as Int32, nan as Int64, c as Char, code, 3 as Float32)
+            }
+        "), Ok(Value::Tuple(vec![
+            Value::UInt8(255), Value::UInt8(44), Value::Int32(i32::MAX), Value::Int32(-2), Value::Int64(0),
+            Value::Char('a'), Value::Int16(66), Value::Float32(3.0),
+        ])));
+    }
+
+    #[test]
+    fn overflow_modes() {
+        let source = "
+            fcn main() -> Int8 {
+                var x :: Int8 = 100
+                x += 100
+                return x
+            }
+        ";
+        assert_eq!(run(source), Err(RuntimeErrorKind::Overflow));
+        assert_eq!(run_with(source, OverflowMode::Wrap), Ok(Value::Int8(-56)));
+
+        let negation = "
+            fcn main() -> Int64 {
+                let x = -9223372036854775807 - 1
+                return -x
+            }
+        ";
+        assert_eq!(run(negation), Err(RuntimeErrorKind::Overflow));
+        assert_eq!(run_with(negation, OverflowMode::Wrap), Ok(Value::Int64(i64::MIN)));
+
+        // Division by zero is an error in both modes
+        assert_eq!(run_with("
+            fcn main() -> Int64 {
+                let x = 0
+                return 1 / x
+            }
+        ", OverflowMode::Wrap), Err(RuntimeErrorKind::DivisionByZero));
+    }
+
+    #[test]
+    fn structs() {
+        assert_eq!(run("
+            struct Line {
+                from :: Point
+                to :: Point
+            }
+
+            struct Point {
+                x :: Int64
+                y :: Int64
+            }
+
+            fcn moved(p :: Point) -> Point {
+                var q = p
+                q.x += 10
+                return q
+            }
+
+            fcn main() -> (Int64, Int64, Bool, Bool) {
+                var line = Line { to: Point { x: 3, y: 4 }, from: Point { x: 1, y: 2 } }
+                let start = line.from
+                line.from.y *= 5
+                let end = moved(line.to)
+                return (start.y + line.from.y, end.x + line.to.x, start == (Point { x: 1, y: 2 }), start != line.from)
+            }
+        "), Ok(Value::Tuple(vec![Value::Int64(12), Value::Int64(16), Value::Bool(true), Value::Bool(true)])));
+    }
+
+    #[test]
+    fn matches() {
+        assert_eq!(run("
+            enum Shape {
+                Circle(Float64)
+                Rect(Float64, Float64)
+                Empty
+            }
+
+            fcn area(s :: Shape) -> Float64 {
+                return match s {
+                    Shape::Circle(r) => r * r * 3.0
+                    Shape::Rect(w, 2.0) => w * 2
+                    Shape::Rect(w, h) => w * h
+                    Shape::Empty => 0
+                }
+            }
+
+            fcn main() -> (Float64, Float64, Float64, Int64, Bool) {
+                let shapes = (Shape::Circle(1), Shape::Rect(3, 2), Shape::Rect(3, 4.5))
+                var n = 0
+                match (shapes.2, 'x') {
+                    (Shape::Rect(_, _), 'y') => { n = 1 }
+                    (_, c) => {
+                        n = c as Int64
+                    }
+                }
+                return (area(shapes.0) + area(Shape::Empty), area(shapes.1), area(shapes.2), n, shapes.0 != shapes.1)
+            }
+        "), Ok(Value::Tuple(vec![
+            Value::Float64(3.0), Value::Float64(6.0), Value::Float64(13.5), Value::Int64(120), Value::Bool(true),
+        ])));
+
+        assert_eq!(run_unchecked("
+            fcn main() -> Int64 {
+                return match 2 {
+                    0 => 0
+                    1 => 1
+                }
+            }
+        "), Err(RuntimeErrorKind::NoMatchingArm));
+
+        assert_eq!(run_unchecked("
+            fcn main() -> Int64 {
+                let (a, 1) = (1, 2)
+                return a
+            }
+        "), Err(RuntimeErrorKind::RefutablePattern));
+    }
+
+    #[test]
+    fn arrays_and_lists() {
+        assert_eq!(run("
+            fcn main() -> (Int8, Int64, Int64, Bool, Int64) {
+                var grid :: [[Int8; 3]; 2] = [[0; 3]; 2]
+                let copy = grid
+                grid[1][2] = 5
+                var l :: List<Int64> = []
+                l.push(4)
+                let length = l.push(6)
+                let last = l.pop()
+                return (grid[1][2] + copy[1][2], length * 10 + last, l[0] + l.len(), copy != grid, [1, 2].len())
+            }
+        "), Ok(Value::Tuple(vec![Value::Int8(5), Value::Int64(26), Value::Int64(5), Value::Bool(true), Value::Int64(2)])));
+
+        assert_eq!(run("
+            fcn main() -> Int64 {
+                let a = [1, 2, 3]
+                let i :: UInt8 = 3
+                return a[i]
+            }
+        "), Err(RuntimeErrorKind::IndexOutOfBounds { index: 3, len: 3 }));
+
+        assert_eq!(run("
+            fcn main() -> Int64 {
+                var l :: List<Int64> = [1]
+                return l[-1]
+            }
+        "), Err(RuntimeErrorKind::IndexOutOfBounds { index: -1, len: 1 }));
+
+        assert_eq!(run("
+            fcn main() -> Int64 {
+                var l :: List<Int64> = [1]
+                return l.pop() + l.pop()
+            }
+        "), Err(RuntimeErrorKind::EmptyList));
+    }
+
+    #[test]
+    fn tuples() {
+        assert_eq!(run("
+            fcn divmod(a :: Int64, b :: Int64) -> (Int64, Int64) {
+                return (a / b, a % b)
+            }
+
+            fcn main() -> (Int64, Bool, Char) {
+                var t = (1, (True, 'c'))
+                t.0 += 1
+                var (q, r) = divmod(17, 5)
+                q *= 10
+                let (_, (flag, c)) = t
+                return (q + r + t.0, flag && t.1.0, c)
+            }
+        "), Ok(Value::Tuple(vec![Value::Int64(34), Value::Bool(true), Value::Char('c')])));
+    }
+
+    #[test]
+    fn generics() {
+        assert_eq!(run("
+            struct Pair<A, B> {
+                first :: A
+                second :: B
+            }
+
+            enum Option<T> {
+                Some(T)
+                None
+            }
+
+            fcn swap<A, B>(p :: Pair<A, B>) -> Pair<B, A> {
+                return Pair { first: p.second, second: p.first }
+            }
+
+            fcn first<T>(l :: List<T>) -> Option<T> {
+                if l.len() == 0 {
+                    return Option::None
+                }
+                return Option::Some(l[0])
+            }
+
+            fcn main() -> (Bool, Int64, Bool, Bool) {
+                let p = swap(Pair { first: 1, second: True })
+                var l :: List<Int8> = []
+                let none = first(l)
+                l.push(7)
+                return (p.first, p.second, none == Option::None, first(l) == Option::Some(7))
+            }
+        "), Ok(Value::Tuple(vec![Value::Bool(true), Value::Int64(1), Value::Bool(true), Value::Bool(true)])));
+    }
+
+    #[test]
+    fn method_dispatch() {
+        assert_eq!(run("
+            struct Point {
+                x :: Int64
+                y :: Int64
+            }
+
+            enum Option<T> {
+                Some(T)
+                None
+            }
+
+            trait Size {
+                fcn size(self) -> Int64
+                fcn larger(self, other :: Self) -> Bool
+            }
+
+            impl Size for Point {
+                fcn size(self) -> Int64 {
+                    return self.x * self.y
+                }
+
+                fcn larger(self, other :: Point) -> Bool {
+                    return self.size() > other.size()
+                }
+            }
+
+            impl<T: Size> Size for Option<T> {
+                fcn size(self) -> Int64 {
+                    return match self {
+                        Option::Some(value) => value.size()
+                        Option::None => 0
+                    }
+                }
+
+                fcn larger(self, other :: Option<T>) -> Bool {
+                    return self.size() > other.size()
+                }
+            }
+
+            impl Size for Int8 {
+                fcn size(self) -> Int64 {
+                    return self as Int64
+                }
+
+                fcn larger(self, other :: Int8) -> Bool {
+                    return self > other
+                }
+            }
+
+            fcn total<T: Size>(a :: T, b :: T) -> Int64 {
+                return if a.larger(b) { a.size() } else { b.size() }
+            }
+
+            fcn main() -> (Int64, Int64, Bool) {
+                let p = Point { x: 2, y: 3 }
+                let o = Option::Some(Point { x: 4, y: 1 })
+                let small :: Int8 = 5
+                return (total(p, Point { x: 1, y: 1 }) + total(small, 9), o.size() + Option::Some(small).size(), o.larger(Option::None))
+            }
+        "), Ok(Value::Tuple(vec![Value::Int64(15), Value::Int64(9), Value::Bool(true)])));
+    }
+
+    #[test]
+    fn remainder_of_min_by_minus_one() {
+        assert_eq!(run("
+            fcn main() -> Int8 {
+                var x :: Int8 = -128
+                return x % -1
+            }
+        "), Ok(Value::Int8(0)));
+    }
+
+    #[test]
+    fn shift_by_amount_of_another_type() {
+        assert_eq!(run("
+            fcn main() -> Int64 {
+                let s :: UInt8 = 3
+                let y :: Int64 = 1 << s
+                var z = 200 << s
+                z >>= s
+                return y + z
+            }
+        "), Ok(Value::Int64(208)));
+    }
+
+    #[test]
+    fn destructuring_in_repl() {
+        let mut checker = Checker::new();
+        let mut interpreter = Interpreter::new();
+        let mut values = Vec::new();
+
+        for source in ["let (a, (b, _)) = (1, (2, 3))", "var (c, d) = (a + b, 10)", "c = c * d", "c"] {
+            let tokens = Lexer::new().lex(source.to_string()).unwrap().clone();
+            for mut input in Parser::new(tokens).parse_interactive().unwrap() {
+                checker.check_input(&mut input).unwrap();
+                match interpreter.eval_input(input).unwrap() {
+                    Some(value) => values.push(value),
+                    None => checker.declare_pending(),
+                }
+            }
+        }
+
+        assert_eq!(values, [Value::Int64(30)]);
+    }
+
+    #[test]
+    fn bitwise_operators() {
+        assert_eq!(run("
+            fcn main() -> (Int8, Int8, UInt8, Int64) {
+                let x :: Int8 = -16
+                let y :: UInt8 = 0b1100
+                return (x >> 2, ~x, y & 0b1010 | y ^ 0xFF, 1 << 62 >> 61)
+            }
+        "), Ok(Value::Tuple(vec![Value::Int8(-4), Value::Int8(15), Value::UInt8(0b1111_1011), Value::Int64(2)])));
+
+        // Bits shifted out are lost
+        assert_eq!(run("
+            fcn main() -> UInt8 {
+                let x :: UInt8 = 0xFF
+                return x << 4
+            }
+        "), Ok(Value::UInt8(0xF0)));
+
+        assert_eq!(run("
+            fcn main() -> Int64 {
+                return 1 << -1
+            }
+        "), Err(RuntimeErrorKind::ShiftOutOfRange));
+    }
+
+    #[test]
+    fn shift_out_of_range() {
+        let source = "
+            fcn main() -> Int8 {
+                let x :: Int8 = 1
+                return x << 300
+            }
+        ";
+        assert_eq!(run(source), Err(RuntimeErrorKind::ShiftOutOfRange));
+        assert_eq!(run_with(source, OverflowMode::Wrap), Err(RuntimeErrorKind::ShiftOutOfRange));
+
+        assert_eq!(run("
+            fcn main() -> Int8 {
+                var x :: Int8 = 1
+                x <<= 8
+                return x
+            }
+        "), Err(RuntimeErrorKind::ShiftOutOfRange));
+    }
+
+    // Runs `test` with the stack the interpreter is given outside of tests
+    fn with_stack(test: impl FnOnce() + Send + 'static) {
+        std::thread::Builder::new().stack_size(STACK_SIZE).spawn(test).unwrap().join().unwrap();
+    }
+
+    #[test]
+    fn deep_recursion() {
+        with_stack(|| assert_eq!(run("
+            fcn f(n :: Int64) -> Int64 {
+                if n == 0 {
+                    return 0
+                }
+                return 1 + f(n - 1)
+            }
+            fcn main() -> Int64 {
+                return f(2000)
+            }
+        "), Ok(Value::Int64(2000))));
+    }
+
+    #[test]
+    fn unbounded_recursion() {
+        with_stack(|| assert_eq!(run("
+            fcn f(n :: Int64) -> Int64 {
+                return 1 + f(n + 1)
+            }
+            fcn main() -> Int64 {
+                return f(0)
+            }
+        "), Err(RuntimeErrorKind::StackOverflow)));
+    }
+
+    #[test]
+    fn deeply_nested_expression() {
+        let source = format!("
+            fcn main() -> Int64 {{
+                return {}1{}
+            }}
+        ", "(1 + ".repeat(80), ")".repeat(80));
+
+        with_stack(move || assert_eq!(run(&source), Ok(Value::Int64(81))));
+    }
+}
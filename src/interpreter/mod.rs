@@ -0,0 +1,5 @@
+#[allow(clippy::module_inception)]
+pub mod interpreter;
+pub mod environment;
+pub mod value;
+pub mod error;
@@ -0,0 +1,522 @@
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::interpreter::error::RuntimeErrorKind;
+use crate::parser::operators::{BinaryOperator, UnaryOperator};
+use crate::parser::tree::Number;
+use crate::parser::types::Type;
+
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+pub enum Value {
+    Int8(i8),
+    Int16(i16),
+    Int32(i32),
+    Int64(i64),
+    Int128(i128),
+    UInt8(u8),
+    UInt16(u16),
+    UInt32(u32),
+    UInt64(u64),
+    UInt128(u128),
+    Float32(f32),
+    Float64(f64),
+    Bool(bool),
+    Char(char),
+    String(String),
+    // Fields in declaration order
+    Struct { name: String, type_args: Vec<Type>, fields: Vec<(String, Value)> },
+    Enum { name: String, type_args: Vec<Type>, variant: String, payload: Vec<Value> },
+    // The element type is kept for empty arrays and lists
+    Array { element_type: Type, elements: Vec<Value> },
+    List { element_type: Type, elements: Vec<Value> },
+    Tuple(Vec<Value>),
+}
+
+// How integer arithmetic behaves when the result does not fit in its type
+#[derive(Debug, Default, Eq, PartialEq, Clone, Copy)]
+pub enum OverflowMode {
+    // Stop with an error
+    #[default]
+    Trap,
+    // Wrap around, keeping the lowest bits of the result
+    Wrap,
+}
+
+// Calls the checked or the wrapping version of an integer method, depending on the mode
+macro_rules! overflowing {
+    ($mode:expr, $value:expr, $checked:ident, $wrapping:ident $(, $arg:expr)?) => {
+        match $mode {
+            OverflowMode::Trap => $value.$checked($($arg)?),
+            OverflowMode::Wrap => Some($value.$wrapping($($arg)?)),
+        }
+    };
+}
+
+// Applies an integer method or a float operator to two values of the same type
+macro_rules! arithmetic {
+    ($left:expr, $right:expr, $checked:ident, $wrapping:ident, $op:tt, $operator:expr, $mode:expr) => {
+        match ($left, $right) {
+            (Value::Int8(a), Value::Int8(b)) => overflowing!($mode, a, $checked, $wrapping, *b).map(Value::Int8),
+            (Value::Int16(a), Value::Int16(b)) => overflowing!($mode, a, $checked, $wrapping, *b).map(Value::Int16),
+            (Value::Int32(a), Value::Int32(b)) => overflowing!($mode, a, $checked, $wrapping, *b).map(Value::Int32),
+            (Value::Int64(a), Value::Int64(b)) => overflowing!($mode, a, $checked, $wrapping, *b).map(Value::Int64),
+            (Value::Int128(a), Value::Int128(b)) => overflowing!($mode, a, $checked, $wrapping, *b).map(Value::Int128),
+            (Value::UInt8(a), Value::UInt8(b)) => overflowing!($mode, a, $checked, $wrapping, *b).map(Value::UInt8),
+            (Value::UInt16(a), Value::UInt16(b)) => overflowing!($mode, a, $checked, $wrapping, *b).map(Value::UInt16),
+            (Value::UInt32(a), Value::UInt32(b)) => overflowing!($mode, a, $checked, $wrapping, *b).map(Value::UInt32),
+            (Value::UInt64(a), Value::UInt64(b)) => overflowing!($mode, a, $checked, $wrapping, *b).map(Value::UInt64),
+            (Value::UInt128(a), Value::UInt128(b)) => overflowing!($mode, a, $checked, $wrapping, *b).map(Value::UInt128),
+            (Value::Float32(a), Value::Float32(b)) => Some(Value::Float32(a $op b)),
+            (Value::Float64(a), Value::Float64(b)) => Some(Value::Float64(a $op b)),
+            (left, _) => return Err(RuntimeErrorKind::InvalidOperand {
+                operator: $operator.to_string(),
+                found: left.value_type(),
+            }),
+        }
+        .ok_or(RuntimeErrorKind::Overflow)
+    };
+}
+
+// Converts a number to a numeric type with the semantics of Rust's 'as': integers
+// are truncated, floats are rounded toward zero and saturated (NaN gives 0)
+macro_rules! cast_number {
+    ($value:expr, $target:expr) => {
+        match $target {
+            Type::Int8 => Some(Value::Int8($value as i8)),
+            Type::Int16 => Some(Value::Int16($value as i16)),
+            Type::Int32 => Some(Value::Int32($value as i32)),
+            Type::Int64 => Some(Value::Int64($value as i64)),
+            Type::Int128 => Some(Value::Int128($value as i128)),
+            Type::UInt8 => Some(Value::UInt8($value as u8)),
+            Type::UInt16 => Some(Value::UInt16($value as u16)),
+            Type::UInt32 => Some(Value::UInt32($value as u32)),
+            Type::UInt64 => Some(Value::UInt64($value as u64)),
+            Type::UInt128 => Some(Value::UInt128($value as u128)),
+            Type::Float32 => Some(Value::Float32($value as f32)),
+            Type::Float64 => Some(Value::Float64($value as f64)),
+            _ => None,
+        }
+    };
+}
+
+// Applies a bitwise operator to two integers of the same type
+macro_rules! bitwise {
+    ($left:expr, $right:expr, $op:tt, $operator:expr) => {
+        match ($left, $right) {
+            (Value::Int8(a), Value::Int8(b)) => Ok(Value::Int8(a $op b)),
+            (Value::Int16(a), Value::Int16(b)) => Ok(Value::Int16(a $op b)),
+            (Value::Int32(a), Value::Int32(b)) => Ok(Value::Int32(a $op b)),
+            (Value::Int64(a), Value::Int64(b)) => Ok(Value::Int64(a $op b)),
+            (Value::Int128(a), Value::Int128(b)) => Ok(Value::Int128(a $op b)),
+            (Value::UInt8(a), Value::UInt8(b)) => Ok(Value::UInt8(a $op b)),
+            (Value::UInt16(a), Value::UInt16(b)) => Ok(Value::UInt16(a $op b)),
+            (Value::UInt32(a), Value::UInt32(b)) => Ok(Value::UInt32(a $op b)),
+            (Value::UInt64(a), Value::UInt64(b)) => Ok(Value::UInt64(a $op b)),
+            (Value::UInt128(a), Value::UInt128(b)) => Ok(Value::UInt128(a $op b)),
+            (left, _) => Err(RuntimeErrorKind::InvalidOperand {
+                operator: $operator.to_string(),
+                found: left.value_type(),
+            }),
+        }
+    };
+}
+
+// Applies a checked shift method to an integer, the amount must be lower
+// than the number of bits of the integer
+macro_rules! shift {
+    ($value:expr, $amount:expr, $checked:ident, $operator:expr) => {
+        match $value {
+            Value::Int8(a) => a.$checked($amount).map(Value::Int8),
+            Value::Int16(a) => a.$checked($amount).map(Value::Int16),
+            Value::Int32(a) => a.$checked($amount).map(Value::Int32),
+            Value::Int64(a) => a.$checked($amount).map(Value::Int64),
+            Value::Int128(a) => a.$checked($amount).map(Value::Int128),
+            Value::UInt8(a) => a.$checked($amount).map(Value::UInt8),
+            Value::UInt16(a) => a.$checked($amount).map(Value::UInt16),
+            Value::UInt32(a) => a.$checked($amount).map(Value::UInt32),
+            Value::UInt64(a) => a.$checked($amount).map(Value::UInt64),
+            Value::UInt128(a) => a.$checked($amount).map(Value::UInt128),
+            value => return Err(RuntimeErrorKind::InvalidOperand {
+                operator: $operator.to_string(),
+                found: value.value_type(),
+            }),
+        }
+        .ok_or(RuntimeErrorKind::ShiftOutOfRange)
+    };
+}
+
+impl Value {
+    // Builds the value of a number literal whose expected type is `value_type`
+    pub fn from_number(number: Number, value_type: Option<&Type>) -> Result<Self, RuntimeErrorKind> {
+        match (number, value_type) {
+            (Number::Integer { negative, magnitude }, _) =>
+                Self::from_integer(negative, magnitude, value_type),
+            (Number::Float(value), Some(Type::Float32)) => Ok(Self::Float32(value as f32)),
+            (Number::Float(value), _) => Ok(Self::Float64(value)),
+        }
+    }
+
+    // Builds the value of an integer literal whose expected type is `value_type`,
+    // integers default to Int64 when nothing is expected
+    pub fn from_integer(negative: bool, magnitude: u128, value_type: Option<&Type>)
+                        -> Result<Self, RuntimeErrorKind> {
+        let value_type = match value_type {
+            Some(t) if t.is_numeric() => t,
+            _ => &Type::Int64,
+        };
+
+        let signed = if negative {
+            0i128.checked_sub_unsigned(magnitude)
+        } else {
+            i128::try_from(magnitude).ok()
+        };
+        let unsigned = if negative && magnitude != 0 { None } else { Some(magnitude) };
+        let float = if negative { -(magnitude as f64) } else { magnitude as f64 };
+
+        let value = match value_type {
+            Type::Int8 => signed.and_then(|v| i8::try_from(v).ok()).map(Self::Int8),
+            Type::Int16 => signed.and_then(|v| i16::try_from(v).ok()).map(Self::Int16),
+            Type::Int32 => signed.and_then(|v| i32::try_from(v).ok()).map(Self::Int32),
+            Type::Int64 => signed.and_then(|v| i64::try_from(v).ok()).map(Self::Int64),
+            Type::Int128 => signed.map(Self::Int128),
+            Type::UInt8 => unsigned.and_then(|v| u8::try_from(v).ok()).map(Self::UInt8),
+            Type::UInt16 => unsigned.and_then(|v| u16::try_from(v).ok()).map(Self::UInt16),
+            Type::UInt32 => unsigned.and_then(|v| u32::try_from(v).ok()).map(Self::UInt32),
+            Type::UInt64 => unsigned.and_then(|v| u64::try_from(v).ok()).map(Self::UInt64),
+            Type::UInt128 => unsigned.map(Self::UInt128),
+            Type::Float32 => Some(Self::Float32(float as f32)),
+            _ => Some(Self::Float64(float)),
+        };
+
+        value.ok_or_else(|| RuntimeErrorKind::LiteralOutOfRange(value_type.clone()))
+    }
+
+    pub fn value_type(&self) -> Type {
+        match self {
+            Self::Int8(_) => Type::Int8,
+            Self::Int16(_) => Type::Int16,
+            Self::Int32(_) => Type::Int32,
+            Self::Int64(_) => Type::Int64,
+            Self::Int128(_) => Type::Int128,
+            Self::UInt8(_) => Type::UInt8,
+            Self::UInt16(_) => Type::UInt16,
+            Self::UInt32(_) => Type::UInt32,
+            Self::UInt64(_) => Type::UInt64,
+            Self::UInt128(_) => Type::UInt128,
+            Self::Float32(_) => Type::Float32,
+            Self::Float64(_) => Type::Float64,
+            Self::Bool(_) => Type::Bool,
+            Self::Char(_) => Type::Char,
+            Self::String(_) => Type::String,
+            Self::Struct { name, type_args, .. } | Self::Enum { name, type_args, .. } =>
+                Type::Named(name.clone(), type_args.clone()),
+            Self::Array { element_type, elements } =>
+                Type::Array(Box::new(element_type.clone()), elements.len()),
+            Self::List { element_type, .. } => Type::List(Box::new(element_type.clone())),
+            Self::Tuple(elements) => Type::Tuple(elements.iter().map(Value::value_type).collect()),
+        }
+    }
+
+    // The fields of a tuple are named by their position
+    pub fn field(&self, name: &str) -> Option<&Value> {
+        match self {
+            Self::Struct { fields, .. } =>
+                fields.iter().find(|(field, _)| field == name).map(|(_, value)| value),
+            Self::Tuple(elements) => elements.get(name.parse::<usize>().ok()?),
+            _ => None,
+        }
+    }
+
+    pub fn field_mut(&mut self, name: &str) -> Option<&mut Value> {
+        match self {
+            Self::Struct { fields, .. } =>
+                fields.iter_mut().find(|(field, _)| field == name).map(|(_, value)| value),
+            Self::Tuple(elements) => elements.get_mut(name.parse::<usize>().ok()?),
+            _ => None,
+        }
+    }
+
+    fn elements(&self) -> Result<&Vec<Value>, RuntimeErrorKind> {
+        match self {
+            Self::Array { elements, .. } | Self::List { elements, .. } => Ok(elements),
+            _ => Err(RuntimeErrorKind::NotIndexable(self.value_type())),
+        }
+    }
+
+    fn elements_mut(&mut self) -> Result<&mut Vec<Value>, RuntimeErrorKind> {
+        let found = self.value_type();
+        match self {
+            Self::Array { elements, .. } | Self::List { elements, .. } => Ok(elements),
+            _ => Err(RuntimeErrorKind::NotIndexable(found)),
+        }
+    }
+
+    // Position designated by `index` among `len` elements, which any integer can give
+    fn position(index: &Value, len: usize) -> Result<usize, RuntimeErrorKind> {
+        let index = index.as_integer().ok_or_else(|| RuntimeErrorKind::InvalidIndex(index.value_type()))?;
+        usize::try_from(index).ok()
+            .filter(|position| *position < len)
+            .ok_or(RuntimeErrorKind::IndexOutOfBounds { index, len })
+    }
+
+    pub fn element(&self, index: &Value) -> Result<&Value, RuntimeErrorKind> {
+        let elements = self.elements()?;
+        Ok(&elements[Self::position(index, elements.len())?])
+    }
+
+    pub fn element_mut(&mut self, index: &Value) -> Result<&mut Value, RuntimeErrorKind> {
+        let elements = self.elements_mut()?;
+        let position = Self::position(index, elements.len())?;
+        Ok(&mut elements[position])
+    }
+
+    // Methods provided by the language, see `BuiltinMethod` for their signatures
+    pub fn call_method(&mut self, method: &str, args: Vec<Value>) -> Result<Value, RuntimeErrorKind> {
+        match (self, method) {
+            (Self::Array { elements, .. } | Self::List { elements, .. }, "len") =>
+                Ok(Value::Int64(elements.len() as i64)),
+            (Self::List { elements, .. }, "push") => {
+                elements.extend(args);
+                Ok(Value::Int64(elements.len() as i64))
+            }
+            (Self::List { elements, .. }, "pop") => elements.pop().ok_or(RuntimeErrorKind::EmptyList),
+            (value, _) => Err(RuntimeErrorKind::UndefinedMethod {
+                found: value.value_type(),
+                method: method.to_string(),
+            }),
+        }
+    }
+
+    // Conversion done by 'as', see `Type::can_cast_to` for the allowed ones
+    pub fn cast(&self, target: &Type) -> Result<Value, RuntimeErrorKind> {
+        let value = match self {
+            _ if self.value_type() == *target => Some(self.clone()),
+            Self::Int8(v) => cast_number!(*v, target),
+            Self::Int16(v) => cast_number!(*v, target),
+            Self::Int32(v) => cast_number!(*v, target),
+            Self::Int64(v) => cast_number!(*v, target),
+            Self::Int128(v) => cast_number!(*v, target),
+            Self::UInt8(v) if *target == Type::Char => Some(Self::Char(char::from(*v))),
+            Self::UInt8(v) => cast_number!(*v, target),
+            Self::UInt16(v) => cast_number!(*v, target),
+            Self::UInt32(v) => cast_number!(*v, target),
+            Self::UInt64(v) => cast_number!(*v, target),
+            Self::UInt128(v) => cast_number!(*v, target),
+            Self::Float32(v) => cast_number!(*v, target),
+            Self::Float64(v) => cast_number!(*v, target),
+            Self::Bool(v) if target.is_integer() => cast_number!(*v as u8, target),
+            Self::Char(v) if target.is_integer() => cast_number!(*v as u32, target),
+            _ => None,
+        };
+
+        value.ok_or_else(|| RuntimeErrorKind::InvalidCast {
+            from: self.value_type(),
+            to: target.clone(),
+        })
+    }
+
+    fn is_integer_zero(&self) -> bool {
+        matches!(self, Self::Int8(0) | Self::Int16(0) | Self::Int32(0) | Self::Int64(0) | Self::Int128(0)
+            | Self::UInt8(0) | Self::UInt16(0) | Self::UInt32(0) | Self::UInt64(0) | Self::UInt128(0))
+    }
+
+    fn as_integer(&self) -> Option<i128> {
+        match self {
+            Self::Int8(v) => Some(*v as i128),
+            Self::Int16(v) => Some(*v as i128),
+            Self::Int32(v) => Some(*v as i128),
+            Self::Int64(v) => Some(*v as i128),
+            Self::Int128(v) => Some(*v),
+            Self::UInt8(v) => Some(*v as i128),
+            Self::UInt16(v) => Some(*v as i128),
+            Self::UInt32(v) => Some(*v as i128),
+            Self::UInt64(v) => Some(*v as i128),
+            // Too big for any shift anyway
+            Self::UInt128(v) => Some(i128::try_from(*v).unwrap_or(i128::MAX)),
+            _ => None,
+        }
+    }
+
+    // Shifting by a negative amount or by at least the number of bits of
+    // the value is an error
+    fn shift(&self, operator: BinaryOperator, amount: &Value) -> Result<Value, RuntimeErrorKind> {
+        let amount = amount.as_integer().ok_or_else(|| RuntimeErrorKind::InvalidOperand {
+            operator: operator.to_string(),
+            found: amount.value_type(),
+        })?;
+        let amount = u32::try_from(amount).map_err(|_| RuntimeErrorKind::ShiftOutOfRange)?;
+
+        match operator {
+            BinaryOperator::ShiftLeft => shift!(self, amount, checked_shl, operator),
+            _ => shift!(self, amount, checked_shr, operator),
+        }
+    }
+
+    pub fn binary_op(&self, operator: BinaryOperator, other: &Value, overflow: OverflowMode)
+                     -> Result<Value, RuntimeErrorKind> {
+        if operator.is_shift() {
+            return self.shift(operator, other);
+        }
+
+        if self.value_type() != other.value_type() {
+            return Err(RuntimeErrorKind::TypeMismatch {
+                expected: self.value_type(),
+                found: other.value_type(),
+            });
+        }
+
+        match (operator, self, other) {
+            (BinaryOperator::Equal, _, _) => return Ok(Value::Bool(self == other)),
+            (BinaryOperator::NotEqual, _, _) => return Ok(Value::Bool(self != other)),
+            (BinaryOperator::And, Self::Bool(a), Self::Bool(b)) => return Ok(Value::Bool(*a && *b)),
+            (BinaryOperator::Or, Self::Bool(a), Self::Bool(b)) => return Ok(Value::Bool(*a || *b)),
+            _ => {}
+        }
+
+        if operator.is_comparison() {
+            let ordering = match self.partial_cmp(other) {
+                Some(ordering) => ordering,
+                // NaN is neither lower nor greater than anything
+                None => return Ok(Value::Bool(false)),
+            };
+
+            return Ok(Value::Bool(match operator {
+                BinaryOperator::Greater => ordering == Ordering::Greater,
+                BinaryOperator::Less => ordering == Ordering::Less,
+                BinaryOperator::GreaterEqual => ordering != Ordering::Less,
+                _ => ordering != Ordering::Greater,
+            }));
+        }
+
+        if matches!(operator, BinaryOperator::Div | BinaryOperator::Mod) && other.is_integer_zero() {
+            return Err(RuntimeErrorKind::DivisionByZero);
+        }
+
+        match operator {
+            BinaryOperator::Add => arithmetic!(self, other, checked_add, wrapping_add, +, operator, overflow),
+            BinaryOperator::Sub => arithmetic!(self, other, checked_sub, wrapping_sub, -, operator, overflow),
+            BinaryOperator::Mul => arithmetic!(self, other, checked_mul, wrapping_mul, *, operator, overflow),
+            BinaryOperator::Div => arithmetic!(self, other, checked_div, wrapping_div, /, operator, overflow),
+            // A remainder always fits, `checked_rem` only fails on MIN % -1 whose result is 0
+            BinaryOperator::Mod => arithmetic!(self, other, checked_rem, wrapping_rem, %, operator, OverflowMode::Wrap),
+            BinaryOperator::BitAnd => bitwise!(self, other, &, operator),
+            BinaryOperator::BitOr => bitwise!(self, other, |, operator),
+            BinaryOperator::BitXor => bitwise!(self, other, ^, operator),
+            _ => Err(RuntimeErrorKind::InvalidOperand {
+                operator: operator.to_string(),
+                found: self.value_type(),
+            }),
+        }
+    }
+
+    pub fn unary_op(&self, operator: UnaryOperator, overflow: OverflowMode) -> Result<Value, RuntimeErrorKind> {
+        let value = match (operator, self) {
+            (UnaryOperator::Neg, Self::Int8(a)) => overflowing!(overflow, a, checked_neg, wrapping_neg).map(Self::Int8),
+            (UnaryOperator::Neg, Self::Int16(a)) => overflowing!(overflow, a, checked_neg, wrapping_neg).map(Self::Int16),
+            (UnaryOperator::Neg, Self::Int32(a)) => overflowing!(overflow, a, checked_neg, wrapping_neg).map(Self::Int32),
+            (UnaryOperator::Neg, Self::Int64(a)) => overflowing!(overflow, a, checked_neg, wrapping_neg).map(Self::Int64),
+            (UnaryOperator::Neg, Self::Int128(a)) => overflowing!(overflow, a, checked_neg, wrapping_neg).map(Self::Int128),
+            (UnaryOperator::Neg, Self::Float32(a)) => Some(Self::Float32(-a)),
+            (UnaryOperator::Neg, Self::Float64(a)) => Some(Self::Float64(-a)),
+            (UnaryOperator::Not, Self::Bool(a)) => Some(Self::Bool(!a)),
+            (UnaryOperator::BitNot, Self::Int8(a)) => Some(Self::Int8(!a)),
+            (UnaryOperator::BitNot, Self::Int16(a)) => Some(Self::Int16(!a)),
+            (UnaryOperator::BitNot, Self::Int32(a)) => Some(Self::Int32(!a)),
+            (UnaryOperator::BitNot, Self::Int64(a)) => Some(Self::Int64(!a)),
+            (UnaryOperator::BitNot, Self::Int128(a)) => Some(Self::Int128(!a)),
+            (UnaryOperator::BitNot, Self::UInt8(a)) => Some(Self::UInt8(!a)),
+            (UnaryOperator::BitNot, Self::UInt16(a)) => Some(Self::UInt16(!a)),
+            (UnaryOperator::BitNot, Self::UInt32(a)) => Some(Self::UInt32(!a)),
+            (UnaryOperator::BitNot, Self::UInt64(a)) => Some(Self::UInt64(!a)),
+            (UnaryOperator::BitNot, Self::UInt128(a)) => Some(Self::UInt128(!a)),
+            _ => return Err(RuntimeErrorKind::InvalidOperand {
+                operator: operator.to_string(),
+                found: self.value_type(),
+            }),
+        };
+
+        value.ok_or(RuntimeErrorKind::Overflow)
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Int8(v) => write!(f, "{}", v),
+            Self::Int16(v) => write!(f, "{}", v),
+            Self::Int32(v) => write!(f, "{}", v),
+            Self::Int64(v) => write!(f, "{}", v),
+            Self::Int128(v) => write!(f, "{}", v),
+            Self::UInt8(v) => write!(f, "{}", v),
+            Self::UInt16(v) => write!(f, "{}", v),
+            Self::UInt32(v) => write!(f, "{}", v),
+            Self::UInt64(v) => write!(f, "{}", v),
+            Self::UInt128(v) => write!(f, "{}", v),
+            Self::Float32(v) => write!(f, "{:?}", v),
+            Self::Float64(v) => write!(f, "{:?}", v),
+            Self::Bool(true) => write!(f, "True"),
+            Self::Bool(false) => write!(f, "False"),
+            Self::Char(v) => write!(f, "{}", v),
+            Self::String(v) => write!(f, "{}", v),
+            Self::Struct { name, fields, .. } => {
+                write!(f, "{} {{", name)?;
+                for (i, (field, value)) in fields.iter().enumerate() {
+                    write!(f, "{}{}: {}", if i == 0 { " " } else { ", " }, field, value)?;
+                }
+                if !fields.is_empty() {
+                    write!(f, " ")?;
+                }
+                write!(f, "}}")
+            }
+            Self::Array { elements, .. } | Self::List { elements, .. } => {
+                let elements = elements.iter().map(Value::to_string).collect::<Vec<_>>();
+                write!(f, "[{}]", elements.join(", "))
+            }
+            Self::Tuple(elements) => {
+                let elements = elements.iter().map(Value::to_string).collect::<Vec<_>>();
+                write!(f, "({})", elements.join(", "))
+            }
+            Self::Enum { name, variant, payload, .. } => {
+                write!(f, "{}::{}", name, variant)?;
+                if !payload.is_empty() {
+                    let payload = payload.iter().map(Value::to_string).collect::<Vec<_>>();
+                    write!(f, "({})", payload.join(", "))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remainder_of_min_by_minus_one() {
+        for overflow in [OverflowMode::Trap, OverflowMode::Wrap] {
+            assert_eq!(Value::Int8(i8::MIN).binary_op(BinaryOperator::Mod, &Value::Int8(-1), overflow), Ok(Value::Int8(0)));
+            assert_eq!(Value::Int64(i64::MIN).binary_op(BinaryOperator::Mod, &Value::Int64(-1), overflow), Ok(Value::Int64(0)));
+            assert_eq!(Value::Int8(-7).binary_op(BinaryOperator::Mod, &Value::Int8(3), overflow), Ok(Value::Int8(-1)));
+        }
+    }
+
+    #[test]
+    fn division_by_zero() {
+        for overflow in [OverflowMode::Trap, OverflowMode::Wrap] {
+            for operator in [BinaryOperator::Div, BinaryOperator::Mod] {
+                assert_eq!(Value::Int8(1).binary_op(operator, &Value::Int8(0), overflow), Err(RuntimeErrorKind::DivisionByZero));
+                assert_eq!(Value::UInt64(1).binary_op(operator, &Value::UInt64(0), overflow), Err(RuntimeErrorKind::DivisionByZero));
+            }
+        }
+    }
+
+    #[test]
+    fn division_of_min_by_minus_one() {
+        assert_eq!(Value::Int8(i8::MIN).binary_op(BinaryOperator::Div, &Value::Int8(-1), OverflowMode::Trap),
+                   Err(RuntimeErrorKind::Overflow));
+        assert_eq!(Value::Int8(i8::MIN).binary_op(BinaryOperator::Div, &Value::Int8(-1), OverflowMode::Wrap),
+                   Ok(Value::Int8(i8::MIN)));
+    }
+}
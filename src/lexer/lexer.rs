@@ -1,26 +1,72 @@
 use std::fmt;
 
-use crate::lexer::lexer::LexerError::{InvalidString, PeekNone};
-use crate::lexer::token::{Token, TokenPosition, TokenType};
+use crate::diagnostics::{diagnostic::Diagnostic, span::Span};
+use crate::lexer::lexer::LexerErrorKind::{
+    InvalidChar, InvalidEscape, InvalidString, PeekNone, UnexpectedChar, UnterminatedChar, UnterminatedComment,
+};
+use crate::lexer::token::{Token, TokenType};
 
-#[derive(Debug, Eq, PartialEq)]
-pub enum LexerError {
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum LexerErrorKind {
     PeekNone,
     InvalidString,
+    InvalidChar,
+    UnterminatedChar,
+    InvalidEscape,
+    UnterminatedComment,
+    UnexpectedChar(char),
 }
 
-impl fmt::Display for LexerError {
+impl fmt::Display for LexerErrorKind {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            LexerError::PeekNone => write!(f, "Peek out of range!"),
-            LexerError::InvalidString => write!(f, "Invalid string! Did you forget a quote?")
+            LexerErrorKind::PeekNone => write!(f, "Peek out of range!"),
+            LexerErrorKind::InvalidString => write!(f, "Invalid string! Did you forget a quote?"),
+            LexerErrorKind::InvalidChar =>
+                write!(f, "Invalid character! A character literal holds exactly one character"),
+            LexerErrorKind::UnterminatedChar => write!(f, "Unterminated character literal!"),
+            LexerErrorKind::InvalidEscape => write!(f, "Invalid escape sequence!"),
+            LexerErrorKind::UnterminatedComment => write!(f, "Unterminated block comment!"),
+            LexerErrorKind::UnexpectedChar(c) => write!(f, "Unexpected character '{}'!", c.escape_default()),
         }
     }
 }
 
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct LexerError {
+    pub kind: LexerErrorKind,
+    pub span: Span,
+}
+
+impl fmt::Display for LexerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl From<&LexerError> for Diagnostic {
+    fn from(err: &LexerError) -> Self {
+        let label = match err.kind {
+            PeekNone => "",
+            InvalidString => "string starts here",
+            InvalidChar => "",
+            UnterminatedChar => "character starts here",
+            InvalidEscape => "expected \\n, \\t, \\r, \\0, \\\\, \\\", \\' or \\u{...}",
+            UnterminatedComment => "comment starts here",
+            UnexpectedChar(_) => "not part of any token",
+        };
+
+        Diagnostic::error(&err.kind.to_string()).with_label(err.span, label)
+    }
+}
+
+#[derive(Default)]
 pub struct Lexer {
     tokens: Vec<Token>,
     input: String,
+    // Characters of the input along with their byte offset,
+    // positions below are indexes in this vector
+    chars: Vec<(usize, char)>,
     current_token_start: usize,
     current_pos: usize,
 }
@@ -30,12 +76,14 @@ impl Lexer {
         Self {
             tokens: Vec::new(),
             input: String::new(),
+            chars: Vec::new(),
             current_token_start: 0,
             current_pos: 0,
         }
     }
 
     pub fn lex(&mut self, input: String) -> Result<&Vec<Token>, LexerError> {
+        self.chars = input.char_indices().collect();
         self.input = input;
 
         loop {
@@ -46,16 +94,24 @@ impl Lexer {
             } else if self.is_digit(self.peek(0)) {
                 self.get_number_token();
             } else if self.is_string(self.peek(0)) {
-                match self.get_string_token() {
-                    Err(err) => return Err(err),
-                    _ => {}
-                }
+                self.get_string_token()?;
+            } else if self.is_label() {
+                self.get_label_token();
+            } else if self.peek(0) == Ok('\'') {
+                self.get_char_token()?;
+            } else if self.is_comment() {
+                self.skip_comment()?;
             } else if self.is_symbol() {
                 self.get_symbol_token();
             } else if self.is_operator() {
                 self.get_operator_token();
             } else if self.is_separator() {
                 self.get_separator_token();
+            } else if let Ok(c) = self.peek(0) {
+                // Whitespace separates tokens, any other character is an error
+                if !c.is_whitespace() {
+                    return Err(self.error(UnexpectedChar(c), self.current_pos));
+                }
             }
 
             if !self.step() {
@@ -63,6 +119,12 @@ impl Lexer {
             }
         }
 
+        self.tokens.push(Token {
+            token_type: TokenType::Eof,
+            content: String::new(),
+            span: Span::new(self.input.len(), self.input.len()),
+        });
+
         Ok(&self.tokens)
     }
 
@@ -74,92 +136,405 @@ impl Lexer {
         false
     }
 
-    fn peek(&self, steps: usize) -> Result<char, LexerError> {
-        if self.current_pos + steps >= self.input.len() {
-            return Err(PeekNone);
+    fn peek(&self, steps: usize) -> Result<char, LexerErrorKind> {
+        match self.chars.get(self.current_pos + steps) {
+            Some((_, c)) => Ok(*c),
+            None => Err(PeekNone),
         }
-        return Ok(self.input.as_bytes()[self.current_pos + steps] as char);
+    }
+
+    // Span going from the character at `start` to the current one
+    fn span_from(&self, start: usize) -> Span {
+        let byte_offset = |pos: usize| match self.chars.get(pos) {
+            Some((offset, _)) => *offset,
+            None => self.input.len(),
+        };
+
+        Span::new(byte_offset(start), byte_offset(self.current_pos + 1))
+    }
+
+    fn error(&self, kind: LexerErrorKind, start: usize) -> LexerError {
+        LexerError { kind, span: self.span_from(start) }
+    }
+
+    // Error spanning the first character of the current token, e.g. the quote
+    // opening a string which is never closed
+    fn opening_error(&self, kind: LexerErrorKind) -> LexerError {
+        let start = self.chars[self.current_token_start].0;
+        LexerError { kind, span: Span::new(start, start + 1) }
     }
 
     fn add_token(&mut self, token_type: TokenType) {
+        let span = self.span_from(self.current_token_start);
+        let content = self.input[span.start..span.end].to_string();
+        self.add_token_with_content(token_type, content);
+    }
+
+    // Strings and characters have their escape sequences decoded in `content`
+    fn add_token_with_content(&mut self, token_type: TokenType, content: String) {
         self.tokens.push(Token {
             token_type,
-            content: self.input[self.current_token_start..self.current_pos + 1].to_string(),
-            position: TokenPosition { start: self.current_token_start, end: self.current_pos },
+            content,
+            span: self.span_from(self.current_token_start),
         })
     }
 
-    fn is_letter(&self, peek: Result<char, LexerError>) -> bool {
-        peek.unwrap_or_default().is_ascii_alphabetic()
+    fn is_letter(&self, peek: Result<char, LexerErrorKind>) -> bool {
+        match peek {
+            Ok(c) => c.is_alphabetic() || c == '_',
+            Err(_) => false,
+        }
     }
 
-    fn is_digit(&self, peek: Result<char, LexerError>) -> bool {
+    fn is_digit(&self, peek: Result<char, LexerErrorKind>) -> bool {
         peek.unwrap_or_default().is_ascii_digit()
     }
 
-    fn is_string(&self, peek: Result<char, LexerError>) -> bool {
+    fn is_string(&self, peek: Result<char, LexerErrorKind>) -> bool {
         peek == Ok('"')
     }
 
     fn is_operator(&self) -> bool {
-        match self.peek(0) {
-            Ok('=') | Ok('+') | Ok('-') |  Ok('*') | Ok('/') | Ok('%') | Ok('>') | Ok('<') => true,
-            _ => false,
-        }
+        matches!(self.peek(0), Ok('=') | Ok('+') | Ok('-') | Ok('*') | Ok('/') | Ok('%') | Ok('>') | Ok('<')
+            | Ok('!') | Ok('&') | Ok('|') | Ok('^') | Ok('~'))
+    }
+
+    fn is_comment(&self) -> bool {
+        self.peek(0) == Ok('/') && matches!(self.peek(1), Ok('/') | Ok('*'))
     }
 
     fn is_symbol(&self) -> bool {
-        return (self.peek(0) == Ok(':') && self.peek(1) == Ok(':'))     // ::
-            || (self.peek(0) == Ok('-') && self.peek(1) == Ok('>'));    // ->
+        self.is_long_symbol() || self.peek(0) == Ok(':')
+    }
+
+    // Symbols made of two characters
+    fn is_long_symbol(&self) -> bool {
+        (self.peek(0) == Ok(':') && self.peek(1) == Ok(':'))     // ::
+            || (self.peek(0) == Ok('-') && self.peek(1) == Ok('>'))  // ->
+            || (self.peek(0) == Ok('.') && self.peek(1) == Ok('.'))  // ..
+            || (self.peek(0) == Ok('=') && self.peek(1) == Ok('>'))  // =>
+    }
+
+    // 'name, unlike a character literal it is not closed by a quote
+    fn is_label(&self) -> bool {
+        self.peek(0) == Ok('\'') && self.is_letter(self.peek(1)) && self.peek(2) != Ok('\'')
     }
 
     fn is_separator(&self) -> bool {
-        match self.peek(0) {
-            Ok('\n') | Ok(',') | Ok('(') | Ok(')') |  Ok('{') | Ok('}') => true,
-            _ => false
-        }
+        matches!(self.peek(0), Ok('\n') | Ok(',') | Ok('(') | Ok(')') | Ok('{') | Ok('}') | Ok('.')
+            | Ok('[') | Ok(']') | Ok(';'))
     }
 
     fn get_keyword_token(&mut self) {
-        while self.is_letter(self.peek(1)) || self.is_digit(self.peek(1)) {
+        while self.is_letter(self.peek(1)) || self.peek(1).is_ok_and(char::is_alphanumeric) {
             self.step();
         }
         self.add_token(TokenType::Keyword);
     }
 
+    // Validity of the literal (digits matching the radix, range...) is checked by the parser
     fn get_number_token(&mut self) {
-        while self.is_digit(self.peek(1)) {
+        // Hexadecimal, binary or octal integer
+        if self.peek(0) == Ok('0') && matches!(self.peek(1), Ok('x') | Ok('b') | Ok('o')) {
             self.step();
+            while self.is_letter(self.peek(1)) || self.is_digit(self.peek(1)) || self.peek(1) == Ok('_') {
+                self.step();
+            }
+            self.add_token(TokenType::Number);
+            return;
         }
+
+        self.skip_digits();
+
+        // Fractional part, the dot must be followed by a digit so that ranges (0..n) stay apart
+        if self.peek(1) == Ok('.') && self.is_digit(self.peek(2)) {
+            self.step();
+            self.skip_digits();
+        }
+
+        // Exponent
+        if matches!(self.peek(1), Ok('e') | Ok('E')) {
+            let sign = matches!(self.peek(2), Ok('+') | Ok('-'));
+            if self.is_digit(self.peek(if sign { 3 } else { 2 })) {
+                self.step();
+                if sign {
+                    self.step();
+                }
+                self.skip_digits();
+            }
+        }
+
         self.add_token(TokenType::Number);
     }
 
+    // Digits can be separated by underscores (1_000_000)
+    fn skip_digits(&mut self) {
+        while self.is_digit(self.peek(1)) || self.peek(1) == Ok('_') {
+            self.step();
+        }
+    }
+
     fn get_string_token(&mut self) -> Result<(), LexerError> {
-        self.step();
+        let mut value = String::new();
 
-        while self.peek(0) != Ok('"') {
-            if !self.step() {
-                return Err(InvalidString);
+        while self.step() {
+            match self.chars[self.current_pos].1 {
+                '"' => {
+                    self.add_token_with_content(TokenType::String, value);
+                    return Ok(());
+                }
+                '\\' => value.push(self.get_escape()?),
+                c => value.push(c),
             }
         }
 
-        self.add_token(TokenType::String);
+        Err(self.opening_error(InvalidString))
+    }
+
+    fn get_char_token(&mut self) -> Result<(), LexerError> {
+        let start = self.current_token_start;
+
+        if !self.step() {
+            return Err(self.opening_error(UnterminatedChar));
+        }
+
+        let value = match self.peek(0) {
+            Ok('\\') => self.get_escape()?,
+            Ok('\n') | Err(_) => return Err(self.opening_error(UnterminatedChar)),
+            Ok('\'') => return Err(self.error(InvalidChar, start)),
+            Ok(c) => c,
+        };
+
+        if self.peek(1) != Ok('\'') {
+            // Several characters are only quoted if a quote follows on the line
+            let closed = self.chars[self.current_pos + 1..].iter()
+                .map(|(_, c)| *c)
+                .take_while(|c| *c != '\n')
+                .any(|c| c == '\'');
+
+            return Err(if closed { self.error(InvalidChar, start) } else { self.opening_error(UnterminatedChar) });
+        }
+        self.step();
+
+        self.add_token_with_content(TokenType::Char, value.to_string());
         Ok(())
     }
 
+    // Decodes the escape sequence starting at the current backslash
+    fn get_escape(&mut self) -> Result<char, LexerError> {
+        let start = self.current_pos;
+
+        if !self.step() {
+            return Err(self.error(InvalidEscape, start));
+        }
+
+        let value = match self.peek(0) {
+            Ok('n') => '\n',
+            Ok('t') => '\t',
+            Ok('r') => '\r',
+            Ok('0') => '\0',
+            Ok('\\') => '\\',
+            Ok('"') => '"',
+            Ok('\'') => '\'',
+            Ok('u') => return self.get_unicode_escape(start),
+            _ => return Err(self.error(InvalidEscape, start)),
+        };
+
+        Ok(value)
+    }
+
+    // \u{XXXX}, with 1 to 6 hexadecimal digits
+    fn get_unicode_escape(&mut self, start: usize) -> Result<char, LexerError> {
+        if self.peek(1) != Ok('{') {
+            return Err(self.error(InvalidEscape, start));
+        }
+        self.step();
+
+        let mut digits = String::new();
+        while digits.len() < 6 && self.peek(1).is_ok_and(|c| c.is_ascii_hexdigit()) {
+            self.step();
+            digits.push(self.peek(0).unwrap_or_default());
+        }
+
+        if self.peek(1) != Ok('}') {
+            return Err(self.error(InvalidEscape, start));
+        }
+        self.step();
+
+        u32::from_str_radix(&digits, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| self.error(InvalidEscape, start))
+    }
+
+    // Comments are dropped, except doc comments (///) standing on their own line
+    // which are kept for the declaration they document
+    fn skip_comment(&mut self) -> Result<(), LexerError> {
+        if self.peek(1) == Ok('*') {
+            return self.skip_block_comment();
+        }
+
+        let is_doc = self.peek(2) == Ok('/') && self.peek(3) != Ok('/');
+        let line_start = match self.tokens.last() {
+            Some(token) => token.is("\n"),
+            None => true,
+        };
+
+        while self.peek(1).is_ok() && self.peek(1) != Ok('\n') {
+            self.step();
+        }
+
+        if is_doc && line_start {
+            self.add_token(TokenType::DocComment);
+        }
+        Ok(())
+    }
+
+    // Block comments can be nested: /* a /* b */ c */
+    fn skip_block_comment(&mut self) -> Result<(), LexerError> {
+        let mut depth = 0;
+
+        loop {
+            if self.peek(0) == Ok('/') && self.peek(1) == Ok('*') {
+                depth += 1;
+                self.step();
+            } else if self.peek(0) == Ok('*') && self.peek(1) == Ok('/') {
+                depth -= 1;
+                self.step();
+
+                if depth == 0 {
+                    return Ok(());
+                }
+            }
+
+            if !self.step() {
+                return Err(LexerError {
+                    kind: UnterminatedComment,
+                    span: Span::new(self.chars[self.current_token_start].0,
+                                    self.chars[self.current_token_start].0 + 2),
+                });
+            }
+        }
+    }
+
     fn get_operator_token(&mut self) {
-        if self.peek(1) == Ok('=') {
+        // Doubled operators (&&, ||, <<, >>)
+        let doubled = matches!(self.peek(0), Ok('&') | Ok('|') | Ok('<') | Ok('>'))
+            && self.peek(1) == self.peek(0);
+        if doubled {
+            self.step();
+        }
+
+        // Operators followed by '=' (==, +=, <=, <<=...), there is no &&= or ||=
+        if self.peek(1) == Ok('=') && !(doubled && matches!(self.peek(0), Ok('&') | Ok('|'))) {
             self.step();
         }
         self.add_token(TokenType::Operator)
     }
 
     fn get_symbol_token(&mut self) {
-        self.step();
+        if self.is_long_symbol() {
+            self.step();
+        }
         self.add_token(TokenType::Symbol);
     }
 
+    fn get_label_token(&mut self) {
+        self.step();
+        while self.is_letter(self.peek(1)) || self.peek(1).is_ok_and(char::is_alphanumeric) {
+            self.step();
+        }
+        self.add_token(TokenType::Label);
+    }
+
     fn get_separator_token(&mut self) {
         self.add_token(TokenType::Separator);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lex_error(input: &str) -> LexerError {
+        Lexer::new().lex(input.to_string()).unwrap_err()
+    }
+
+    // Content of the tokens of a given type
+    fn contents(input: &str, token_type: TokenType) -> Vec<String> {
+        Lexer::new().lex(input.to_string()).unwrap().iter()
+            .filter(|token| token.token_type == token_type)
+            .map(|token| token.content.clone())
+            .collect()
+    }
+
+    #[test]
+    fn number_literals() {
+        assert_eq!(contents("1_000 3.14 1e-9 2.5E3 0x1F 0b101 0o17 -42", TokenType::Number),
+                   ["1_000", "3.14", "1e-9", "2.5E3", "0x1F", "0b101", "0o17", "42"]);
+        // The dot of a range is not a fractional part
+        assert_eq!(contents("0..10", TokenType::Number), ["0", "10"]);
+    }
+
+    #[test]
+    fn comments() {
+        let input = "a // line\n/* block /* nested */ still */ b\n/// doc\nc /// not a doc";
+        assert_eq!(contents(input, TokenType::Keyword), ["a", "b", "c"]);
+        assert_eq!(contents(input, TokenType::DocComment), ["/// doc"]);
+
+        assert_eq!(lex_error("a /* open /* nested */"), LexerError { kind: UnterminatedComment, span: Span::new(2, 4) });
+    }
+
+    #[test]
+    fn unexpected_chars() {
+        assert_eq!(lex_error("a $ b"), LexerError { kind: UnexpectedChar('$'), span: Span::new(2, 3) });
+    }
+
+    #[test]
+    fn escapes() {
+        assert_eq!(contents(r#""a\tb\n\\ \"q\" \u{E9}\u{1F600}""#, TokenType::String), ["a\tb\n\\ \"q\" é😀"]);
+
+        // The span goes from the backslash to the end of the sequence
+        assert_eq!(lex_error(r#"x = "a\q""#), LexerError { kind: InvalidEscape, span: Span::new(6, 8) });
+        assert_eq!(lex_error(r#"x = "\u{D800}""#).kind, InvalidEscape);
+        assert_eq!(lex_error(r#"x = "\u{}""#).kind, InvalidEscape);
+        assert_eq!(lex_error(r#"x = "\u41""#).kind, InvalidEscape);
+    }
+
+    #[test]
+    fn utf8_input() {
+        let tokens = Lexer::new().lex("été = 'é'".to_string()).unwrap().clone();
+
+        assert_eq!(tokens[0].content, "été");
+        assert_eq!(tokens[0].span, Span::new(0, 5));
+        assert_eq!(tokens[2].content, "é");
+        assert_eq!(tokens[2].span, Span::new(8, 12));
+    }
+
+    #[test]
+    fn char_literals() {
+        let tokens = Lexer::new().lex("'a' '\\n'".to_string()).unwrap().clone();
+        let chars = tokens.iter()
+            .filter(|token| token.token_type == TokenType::Char)
+            .map(|token| token.content.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(chars, ["a", "\n"]);
+
+        assert_eq!(lex_error("x = ''").kind, InvalidChar);
+        assert_eq!(lex_error("x = '12'").kind, InvalidChar);
+    }
+
+    #[test]
+    fn unterminated_char_literals() {
+        // The span is the opening quote, wherever the literal stops
+        for input in ["x = '", "x = '1", "x = '1 + 2\ny = 3", "x = '\n'"] {
+            assert_eq!(lex_error(input), LexerError { kind: UnterminatedChar, span: Span::new(4, 5) }, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn unterminated_strings() {
+        assert_eq!(lex_error("x = \"abc"), LexerError { kind: InvalidString, span: Span::new(4, 5) });
+        assert_eq!(lex_error("x = \"abc\\\""), LexerError { kind: InvalidString, span: Span::new(4, 5) });
+    }
+}
@@ -1,2 +1,3 @@
+#[allow(clippy::module_inception)]
 pub mod lexer;
-pub mod token;
\ No newline at end of file
+pub mod token;
@@ -1,8 +1,38 @@
-#[derive(Debug, Clone)]
+use std::fmt;
+
+use crate::diagnostics::span::Span;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
 pub struct Token {
     pub token_type: TokenType,
     pub content: String,
-    pub position: TokenPosition,
+    pub span: Span,
+}
+
+impl Token {
+    // The content of literals is their decoded value, which must not be
+    // mistaken for a keyword or a separator
+    pub fn is_literal(&self) -> bool {
+        matches!(self.token_type, TokenType::Number | TokenType::String | TokenType::Char)
+    }
+
+    // Whether the token is a keyword, symbol, operator or separator equal to `content`
+    pub fn is(&self, content: &str) -> bool {
+        !self.is_literal() && self.content == content
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.token_type {
+            TokenType::Eof => write!(f, "end of input"),
+            TokenType::String => write!(f, "\"{}\"", self.content.escape_debug()),
+            TokenType::Char => write!(f, "'{}'", self.content.escape_debug()),
+            TokenType::Label => write!(f, "{}", self.content),
+            _ if self.content == "\n" => write!(f, "new line"),
+            _ => write!(f, "'{}'", self.content),
+        }
+    }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
@@ -10,13 +40,11 @@ pub enum TokenType {
     Keyword,
     Number,
     String,
+    Char,
     Operator,
     Symbol,
     Separator,
+    Label,
+    DocComment,
+    Eof,
 }
-
-#[derive(Debug, Clone)]
-pub struct TokenPosition {
-    pub start: usize,
-    pub end: usize,
-}
\ No newline at end of file
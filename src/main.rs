@@ -1,7 +1,21 @@
-mod lexer;
+use std::env;
+use std::thread;
+
+use renek_lang::interpreter::interpreter::STACK_SIZE;
+
 mod cli;
-mod parser;
 
 fn main() {
-    cli::interactive();
-}
\ No newline at end of file
+    let options = cli::Options::from_args(env::args().skip(1));
+
+    // The checker and the interpreter recurse over the syntax tree, deeper than
+    // the main thread allows
+    let child = thread::Builder::new().stack_size(STACK_SIZE).spawn(move || {
+        match &options.file {
+            Some(path) => cli::run_file(path, &options),
+            None => cli::interactive(&options),
+        }
+    }).expect("Could not start the interpreter thread");
+
+    child.join().expect("The interpreter thread panicked");
+}
@@ -0,0 +1,79 @@
+use std::fmt;
+
+use crate::diagnostics::{diagnostic::Diagnostic, span::Span};
+use crate::lexer::token::Token;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum ParseErrorKind {
+    UnexpectedToken,
+    InvalidName,
+    InvalidType,
+    InvalidNumber,
+    // Bits the number does not fit in
+    NumberTooLarge(u32),
+    MissingExpression,
+    UnclosedBlock,
+    TooDeeplyNested,
+    StrayDocComment,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseErrorKind::UnexpectedToken => write!(f, "Unexpected token"),
+            ParseErrorKind::InvalidName => write!(f, "Invalid name"),
+            ParseErrorKind::InvalidType => write!(f, "Invalid type"),
+            ParseErrorKind::InvalidNumber => write!(f, "Invalid number literal"),
+            ParseErrorKind::NumberTooLarge(bits) => write!(f, "Number literal does not fit in {} bits", bits),
+            ParseErrorKind::MissingExpression => write!(f, "Missing expression"),
+            ParseErrorKind::UnclosedBlock => write!(f, "Unclosed block"),
+            ParseErrorKind::TooDeeplyNested => write!(f, "Too deeply nested"),
+            ParseErrorKind::StrayDocComment => write!(f, "Doc comment does not document anything"),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub token: Token,
+    pub span: Span,
+    // What the parser would have accepted instead of `token`
+    pub expected: Vec<String>,
+}
+
+impl ParseError {
+    // "expected <x>, <y> or <z>, got <token>"
+    pub fn expectation(&self) -> String {
+        let mut output = String::new();
+
+        if let Some((last, others)) = self.expected.split_last() {
+            output.push_str("expected ");
+            if !others.is_empty() {
+                output.push_str(&format!("{} or ", others.join(", ")));
+            }
+            output.push_str(&format!("{}, ", last));
+        }
+
+        output.push_str(&format!("got {}", self.token));
+        output
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.kind, self.expectation())
+    }
+}
+
+impl From<&ParseError> for Diagnostic {
+    fn from(err: &ParseError) -> Self {
+        let label = match err.kind {
+            // Nothing was expected instead of the comment
+            ParseErrorKind::StrayDocComment => "no declaration follows".to_string(),
+            _ => err.expectation(),
+        };
+
+        Diagnostic::error(&err.kind.to_string()).with_label(err.span, &label)
+    }
+}
@@ -1,4 +1,8 @@
+#[allow(clippy::module_inception)]
 pub mod parser;
-mod tree;
-mod types;
-mod statements;
+pub mod tree;
+pub mod types;
+pub mod statements;
+pub mod variables;
+pub mod operators;
+pub mod error;
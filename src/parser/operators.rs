@@ -0,0 +1,220 @@
+use std::fmt;
+
+use crate::lexer::token::{Token, TokenType};
+use crate::parser::types::Type;
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum BinaryOperator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    Greater,
+    Less,
+    GreaterEqual,
+    LessEqual,
+    Equal,
+    NotEqual,
+    And,
+    Or,
+    BitAnd,
+    BitOr,
+    BitXor,
+    ShiftLeft,
+    ShiftRight,
+}
+
+impl BinaryOperator {
+    pub fn from_token(token: &Token) -> Option<Self> {
+        // Logical operators can also be written as keywords
+        if token.token_type == TokenType::Keyword {
+            return match token.content.as_str() {
+                "and" => Some(Self::And),
+                "or" => Some(Self::Or),
+                _ => None,
+            };
+        }
+
+        if token.token_type != TokenType::Operator {
+            return None;
+        }
+
+        match token.content.as_str() {
+            "+" => Some(Self::Add),
+            "-" => Some(Self::Sub),
+            "*" => Some(Self::Mul),
+            "/" => Some(Self::Div),
+            "%" => Some(Self::Mod),
+            ">" => Some(Self::Greater),
+            "<" => Some(Self::Less),
+            ">=" => Some(Self::GreaterEqual),
+            "<=" => Some(Self::LessEqual),
+            "==" => Some(Self::Equal),
+            "!=" => Some(Self::NotEqual),
+            "&&" => Some(Self::And),
+            "||" => Some(Self::Or),
+            "&" => Some(Self::BitAnd),
+            "|" => Some(Self::BitOr),
+            "^" => Some(Self::BitXor),
+            "<<" => Some(Self::ShiftLeft),
+            ">>" => Some(Self::ShiftRight),
+            _ => None,
+        }
+    }
+
+    // Higher binds tighter, every binary operator is left-associative.
+    // The order is the one of C
+    pub fn precedence(&self) -> u8 {
+        match self {
+            Self::Or => 1,
+            Self::And => 2,
+            Self::BitOr => 3,
+            Self::BitXor => 4,
+            Self::BitAnd => 5,
+            Self::Equal | Self::NotEqual => 6,
+            Self::Greater | Self::Less | Self::GreaterEqual | Self::LessEqual => 7,
+            Self::ShiftLeft | Self::ShiftRight => 8,
+            Self::Add | Self::Sub => 9,
+            Self::Mul | Self::Div | Self::Mod => 10,
+        }
+    }
+
+    // Whether the operator can be applied to operands of type `operand`
+    pub fn accepts(&self, operand: &Type) -> bool {
+        match self {
+            Self::Equal | Self::NotEqual => true,
+            Self::Greater | Self::Less | Self::GreaterEqual | Self::LessEqual =>
+                operand.is_numeric() || *operand == Type::Char || *operand == Type::String,
+            Self::And | Self::Or => *operand == Type::Bool,
+            Self::BitAnd | Self::BitOr | Self::BitXor | Self::ShiftLeft | Self::ShiftRight =>
+                operand.is_integer(),
+            Self::Add | Self::Sub | Self::Mul | Self::Div | Self::Mod => operand.is_numeric(),
+        }
+    }
+
+    // Operators comparing two values of the same type
+    pub fn is_comparison(&self) -> bool {
+        matches!(self, Self::Greater | Self::Less | Self::GreaterEqual | Self::LessEqual | Self::Equal
+            | Self::NotEqual)
+    }
+
+    // Operators on booleans, whose right operand is only evaluated if needed
+    pub fn is_logical(&self) -> bool {
+        matches!(self, Self::And | Self::Or)
+    }
+
+    // Shifts take an amount of any integer type
+    pub fn is_shift(&self) -> bool {
+        matches!(self, Self::ShiftLeft | Self::ShiftRight)
+    }
+}
+
+impl fmt::Display for BinaryOperator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let symbol = match self {
+            Self::Add => "+",
+            Self::Sub => "-",
+            Self::Mul => "*",
+            Self::Div => "/",
+            Self::Mod => "%",
+            Self::Greater => ">",
+            Self::Less => "<",
+            Self::GreaterEqual => ">=",
+            Self::LessEqual => "<=",
+            Self::Equal => "==",
+            Self::NotEqual => "!=",
+            Self::And => "&&",
+            Self::Or => "||",
+            Self::BitAnd => "&",
+            Self::BitOr => "|",
+            Self::BitXor => "^",
+            Self::ShiftLeft => "<<",
+            Self::ShiftRight => ">>",
+        };
+        write!(f, "{}", symbol)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum UnaryOperator {
+    Neg,
+    Not,
+    BitNot,
+}
+
+impl UnaryOperator {
+    pub fn from_token(token: &Token) -> Option<Self> {
+        if token.token_type == TokenType::Keyword && token.content == "not" {
+            return Some(Self::Not);
+        }
+
+        if token.token_type != TokenType::Operator {
+            return None;
+        }
+
+        match token.content.as_str() {
+            "-" => Some(Self::Neg),
+            "!" => Some(Self::Not),
+            "~" => Some(Self::BitNot),
+            _ => None,
+        }
+    }
+
+    pub fn accepts(&self, operand: &Type) -> bool {
+        match self {
+            Self::Neg => operand.is_numeric() && !operand.is_unsigned(),
+            Self::Not => *operand == Type::Bool,
+            Self::BitNot => operand.is_integer(),
+        }
+    }
+}
+
+impl fmt::Display for UnaryOperator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Neg => write!(f, "-"),
+            Self::Not => write!(f, "!"),
+            Self::BitNot => write!(f, "~"),
+        }
+    }
+}
+
+// '=' or an operator applied to the assigned variable, such as '+='
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum AssignOperator {
+    Assign,
+    Compound(BinaryOperator),
+}
+
+impl AssignOperator {
+    pub fn from_token(token: &Token) -> Option<Self> {
+        if token.token_type != TokenType::Operator {
+            return None;
+        }
+
+        match token.content.as_str() {
+            "=" => Some(Self::Assign),
+            "+=" => Some(Self::Compound(BinaryOperator::Add)),
+            "-=" => Some(Self::Compound(BinaryOperator::Sub)),
+            "*=" => Some(Self::Compound(BinaryOperator::Mul)),
+            "/=" => Some(Self::Compound(BinaryOperator::Div)),
+            "%=" => Some(Self::Compound(BinaryOperator::Mod)),
+            "&=" => Some(Self::Compound(BinaryOperator::BitAnd)),
+            "|=" => Some(Self::Compound(BinaryOperator::BitOr)),
+            "^=" => Some(Self::Compound(BinaryOperator::BitXor)),
+            "<<=" => Some(Self::Compound(BinaryOperator::ShiftLeft)),
+            ">>=" => Some(Self::Compound(BinaryOperator::ShiftRight)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AssignOperator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Assign => write!(f, "="),
+            Self::Compound(operator) => write!(f, "{}=", operator),
+        }
+    }
+}
@@ -1,31 +1,129 @@
+use std::mem;
+use std::num::{IntErrorKind, ParseIntError};
+
+use crate::diagnostics::span::Span;
 use crate::lexer::token::{Token, TokenType};
 use crate::parser::{
     tree::{
-        Node, ParseTree, BoolNode, FunctionCallNode, NumberNode, StringNode, VariableCallNode,
-        VariableNode, BlockNode, FunctionNode, FunctionArgNode, StatementNode,
+        ParseTree, Item, Input, Stmt, Expr, BoolNode, FunctionCallNode, Number, NumberNode, StringNode,
+        CharNode,
+        VariableCallNode, VariableNode, BlockNode, FunctionNode, FunctionArgNode, StatementNode,
+        BinaryOpNode, UnaryOpNode, CastNode, IfNode, ElseBranch, LoopNode, LoopKind, AssignNode,
+        StructNode, StructFieldNode, StructLiteralNode, FieldValueNode, FieldNode, EnumNode,
+        EnumVariantNode, VariantCallNode, MatchNode, MatchArmNode, Pattern, ArrayNode, ArrayElements,
+        IndexNode, MethodCallNode, TupleNode, DestructureNode, BoundNode, TraitNode, MethodDeclNode,
+        ImplNode,
     },
     types::Type,
     statements::Statement,
+    variables::VariableKind,
+    operators::{AssignOperator, BinaryOperator, UnaryOperator},
+    error::{ParseError, ParseErrorKind},
 };
 
+// `Ok(None)` means the tokens do not start the construct, `Err` means they start it
+// but are malformed
+type ParseResult<T> = Result<Option<T>, ParseError>;
+
+// Deeper nesting is reported instead of overflowing the native stack while
+// parsing, checking or running the tree
+const MAX_NESTING: usize = 256;
+
 pub struct Parser {
     tokens: Vec<Token>,
-    tree: ParseTree,
+    errors: Vec<ParseError>,
     current_pos: usize,
+    // False in the head of an 'if' or a loop, where `name {` opens the block
+    // instead of a struct literal
+    struct_literals: bool,
+    // Type parameters of the generic declaration being parsed, and their bounds
+    type_params: Vec<String>,
+    bounds: Vec<BoundNode>,
+    // Type designated by `Self` in the trait or impl being parsed
+    self_type: Option<Type>,
+    // Nesting of the expression, type, pattern or block being parsed
+    depth: usize,
+    // Positions of the doc comments picked up by a declaration
+    documented: Vec<usize>,
 }
 
 impl Parser {
     pub fn new(tokens: Vec<Token>) -> Self {
         Self {
             tokens,
-            tree: ParseTree::new(),
+            errors: Vec::new(),
             current_pos: 0,
+            struct_literals: true,
+            type_params: Vec::new(),
+            bounds: Vec::new(),
+            self_type: None,
+            depth: 0,
+            documented: Vec::new(),
+        }
+    }
+
+    pub fn parse(mut self) -> Result<ParseTree, Vec<ParseError>> {
+        let nodes = self.parse_sequence(Self::parse_item, &["'fcn'", "'struct'", "'enum'", "'trait'", "'impl'", "'var'", "'let'", "'const'"]);
+        self.report_stray_docs();
+
+        if self.errors.is_empty() {
+            Ok(ParseTree { nodes })
+        } else {
+            Err(self.errors)
+        }
+    }
+
+    // Unlike a program, the interactive interpreter also accepts statements and
+    // expressions at the top level
+    pub fn parse_interactive(mut self) -> Result<Vec<Input>, Vec<ParseError>> {
+        let inputs = self.parse_sequence(Self::parse_input, &["item", "statement"]);
+        self.report_stray_docs();
+
+        if self.errors.is_empty() {
+            Ok(inputs)
+        } else {
+            Err(self.errors)
         }
     }
 
-    pub fn parse(&mut self) {
-        let func = self.parse_func_decl();
-        println!("{:?}", func);
+    // Parses line-separated nodes until the end of input
+    fn parse_sequence<T>(&mut self, parse_one: fn(&mut Self) -> ParseResult<T>,
+                         expected: &[&str]) -> Vec<T> {
+        let mut nodes = Vec::new();
+
+        loop {
+            self.skip_newlines();
+
+            if self.equals_type(TokenType::Eof) {
+                break;
+            }
+
+            match parse_one(self) {
+                Ok(Some(node)) => nodes.push(node),
+                Ok(None) => {
+                    let err = self.error(ParseErrorKind::UnexpectedToken, expected);
+                    self.errors.push(err);
+                    self.synchronize_item();
+                    continue;
+                }
+                Err(err) => {
+                    self.errors.push(err);
+                    self.synchronize_item();
+                    continue;
+                }
+            }
+
+            // Skip the last token of the item
+            self.step(1);
+
+            if !self.equals_content("\n") && !self.equals_type(TokenType::Eof) {
+                let err = self.error(ParseErrorKind::UnexpectedToken, &["new line"]);
+                self.errors.push(err);
+                self.synchronize_item();
+            }
+        }
+
+        nodes
     }
 
     fn step(&mut self, n: usize) -> bool {
@@ -52,7 +150,7 @@ impl Parser {
 
     fn equals_content(&self, content: &str) -> bool {
         match self.peek(0) {
-            Some(token) => token.content == content,
+            Some(token) => token.is(content),
             _ => false
         }
     }
@@ -66,380 +164,1926 @@ impl Parser {
                     return true;
                 }
 
-                return match token.content.as_str() {
-                    "var" | "if" | "else" | "return" => true,
-                    _ => false,
-                };
+                matches!(token.content.as_str(), "var" | "let" | "const" | "if" | "else" | "return"
+                    | "while" | "loop" | "for" | "in" | "break" | "continue" | "and" | "or" | "not" | "as" | "struct" | "enum"
+                    | "match" | "List" | "trait" | "impl" | "Self")
             }
             _ => false
         }
     }
 
-    fn expected(&self, what: &str) {
-        println!("Expected {}, got {}", what, self.peek(0).unwrap().content);
+    fn is_name(&self) -> bool {
+        self.equals_type(TokenType::Keyword) && !self.is_forbidden_keyword()
     }
 
-    fn parse_node(&mut self) -> Option<Node> {
-        while self.equals_content("\n") {
-            if !self.step(1) {
-                println!("Peek out of range");
-                return None;
-            }
-        }
+    // Doc comments are skipped as well, they are picked up by `parse_doc`
+    fn skip_newlines(&mut self) {
+        while (self.equals_content("\n") || self.equals_type(TokenType::DocComment))
+            && self.step(1) {}
+    }
 
-        // 6 possibilities :
-        //  - variable declaration
-        //  - function call
-        //  - condition
-        //  - loop
-        //  - block
-        //  - statement (return, break...)
-        let var_decl = self.parse_var_decl();
-        if var_decl.is_some() {
-            return var_decl;
-        }
+    // Joins the doc comments preceding the current token, one per line
+    fn parse_doc(&mut self) -> Option<String> {
+        let positions = (0..self.current_pos)
+            .rev()
+            .take_while(|&pos| self.tokens[pos].is("\n") || self.tokens[pos].token_type == TokenType::DocComment)
+            .filter(|&pos| self.tokens[pos].token_type == TokenType::DocComment)
+            .collect::<Vec<_>>();
 
-        let func_call = self.parse_func_call();
-        if func_call.is_some() {
-            return func_call;
+        if positions.is_empty() {
+            return None;
         }
+        self.documented.extend(&positions);
 
-        let block = self.parse_block();
-        if block.is_some() {
-            return block;
-        }
+        let lines = positions.iter()
+            .rev()
+            .map(|&pos| {
+                let line = self.tokens[pos].content.trim_start_matches('/');
+                line.strip_prefix(' ').unwrap_or(line)
+            })
+            .collect::<Vec<_>>();
+
+        Some(lines.join("\n"))
+    }
 
-        let statement = self.parse_statement();
-        if statement.is_some() {
-            return statement;
+    // Doc comments no declaration picked up, e.g. above a statement or at the end
+    // of a block. Consecutive ones are reported once
+    fn report_stray_docs(&mut self) {
+        let mut previous_is_doc = false;
+
+        for (pos, token) in self.tokens.iter().enumerate() {
+            if token.is("\n") {
+                continue;
+            }
+
+            let is_doc = token.token_type == TokenType::DocComment;
+            if is_doc && !previous_is_doc && !self.documented.contains(&pos) {
+                self.errors.push(ParseError {
+                    kind: ParseErrorKind::StrayDocComment,
+                    span: token.span,
+                    token: token.clone(),
+                    expected: Vec::new(),
+                });
+            }
+            previous_is_doc = is_doc;
         }
 
-        None
+        self.errors.sort_by_key(|err| err.span.start);
     }
 
-    fn parse_expr(&mut self) -> Option<Node> {
-        // 4 possibilities :
-        //  - operation
-        //  - fixed value (boolean, integer...)
-        //  - variable call
-        //  - function call
-        let value = self.parse_value();
-        if value.is_some() {
-            return value;
+    // Skip the rest of a malformed line so parsing can resume on the next one
+    fn synchronize(&mut self) {
+        while !self.equals_content("\n") && !self.equals_content("}")
+            && !self.equals_type(TokenType::Eof) && self.step(1) {}
+    }
+
+    fn current_token(&self) -> Token {
+        // Past the last token, fall back to the last one
+        match self.peek(0).or_else(|| self.tokens.last()) {
+            Some(token) => token.clone(),
+            None => Token {
+                token_type: TokenType::Eof,
+                content: String::new(),
+                span: Span::default(),
+            },
         }
+    }
 
-        let func_call = self.parse_func_call();
-        if func_call.is_some() {
-            return func_call;
+    // Span going from `start` to the current token, which is the last
+    // token of the node being parsed
+    fn span_from(&self, start: Span) -> Span {
+        start.to(self.current_token().span)
+    }
+
+    // Skip everything up to the start of the next top-level item
+    fn synchronize_item(&mut self) {
+        let mut depth = 0usize;
+
+        while self.step(1) {
+            if self.equals_content("{") {
+                depth += 1;
+            } else if self.equals_content("}") {
+                depth = depth.saturating_sub(1);
+            } else if depth == 0 && (self.equals_content("fcn") || self.equals_content("struct")
+                || self.equals_content("enum") || self.equals_content("trait") || self.equals_content("impl")
+                || self.peek(0).and_then(VariableKind::from_token).is_some()) {
+                break;
+            }
         }
+    }
+
+    fn error(&self, kind: ParseErrorKind, expected: &[&str]) -> ParseError {
+        let token = self.current_token();
 
-        let var_call = self.parse_var_call();
-        if var_call.is_some() {
-            return var_call;
+        ParseError {
+            kind,
+            span: token.span,
+            token,
+            expected: expected.iter().map(|e| e.to_string()).collect(),
         }
+    }
 
-        None
+    fn expected_expr(&self) -> ParseError {
+        self.error(ParseErrorKind::MissingExpression, &["expression"])
     }
 
-    fn parse_value(&mut self) -> Option<Node> {
-        if self.equals_type(TokenType::Keyword) {
-            return self.parse_bool();
-        } else if self.equals_type(TokenType::Number) {
-            return self.parse_number();
-        } else if self.equals_type(TokenType::String) {
-            return self.parse_string();
+    fn expect_content(&self, content: &str) -> Result<(), ParseError> {
+        if self.equals_content(content) {
+            return Ok(());
         }
-        None
+        Err(self.error(ParseErrorKind::UnexpectedToken, &[&format!("'{}'", content)]))
     }
 
-    fn parse_bool(&mut self) -> Option<Node> {
-        if self.equals_content("True") {
-            return Some(Box::new(BoolNode { value: true }));
-        } else if self.equals_content("False") {
-            return Some(Box::new(BoolNode { value: false }));
+    // Runs `parse` with struct literals allowed or not
+    fn with_struct_literals<T>(&mut self, allowed: bool, parse: fn(&mut Self) -> T) -> T {
+        let previous = mem::replace(&mut self.struct_literals, allowed);
+        let result = parse(self);
+        self.struct_literals = previous;
+        result
+    }
+
+    // Runs `parse` one level deeper, the depth being restored even if it fails
+    fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> Result<T, ParseError>) -> Result<T, ParseError> {
+        let depth = self.depth;
+        let result = self.deepen().and_then(|_| parse(self));
+        self.depth = depth;
+        result
+    }
+
+    // Chains of operations, casts or postfixes nest without recursing, so they
+    // go one level deeper per link until `nested` restores the depth
+    fn deepen(&mut self) -> Result<(), ParseError> {
+        self.depth += 1;
+        if self.depth > MAX_NESTING {
+            let limit = format!("at most {} levels of nesting", MAX_NESTING);
+            return Err(self.error(ParseErrorKind::TooDeeplyNested, &[&limit]));
         }
-        None
+        Ok(())
     }
 
-    fn parse_number(&mut self) -> Option<Node> {
-        Some(Box::new(NumberNode {
-            int_value: self.peek(0).unwrap().content.parse::<usize>().unwrap()
-        }))
+    // Expression right before the block of an 'if' or a loop
+    fn parse_head_expr(&mut self) -> ParseResult<Expr> {
+        self.with_struct_literals(false, Self::parse_expr)
     }
 
-    fn parse_string(&mut self) -> Option<Node> {
-        Some(Box::new(StringNode { value: self.peek(0).unwrap().content.clone() }))
+    fn parse_item(&mut self) -> ParseResult<Item> {
+        // Left over by a malformed generic declaration, trait or impl
+        self.type_params.clear();
+        self.bounds.clear();
+        self.self_type = None;
+
+        // 6 possibilities :
+        //  - function declaration
+        //  - struct declaration
+        //  - enum declaration
+        //  - trait declaration
+        //  - impl
+        //  - global variable declaration
+        if let Some(func_decl) = self.parse_func_decl()? {
+            return Ok(Some(Item::Function(func_decl)));
+        }
+
+        if let Some(struct_decl) = self.parse_struct_decl()? {
+            return Ok(Some(Item::Struct(struct_decl)));
+        }
+
+        if let Some(enum_decl) = self.parse_enum_decl()? {
+            return Ok(Some(Item::Enum(enum_decl)));
+        }
+
+        if let Some(trait_decl) = self.parse_trait_decl()? {
+            return Ok(Some(Item::Trait(trait_decl)));
+        }
+
+        if let Some(impl_decl) = self.parse_impl()? {
+            return Ok(Some(Item::Impl(impl_decl)));
+        }
+
+        Ok(self.parse_var_decl()?.map(Item::Variable))
     }
 
-    fn parse_var_decl(&mut self) -> Option<Node> {
-        if !self.equals_content("var") {
-            return None;
+    fn parse_input(&mut self) -> ParseResult<Input> {
+        // Would be taken for a malformed variable declaration by `parse_item`
+        if let Some(destructure) = self.parse_destructure()? {
+            return Ok(Some(Input::Stmt(Stmt::Destructure(destructure))));
         }
 
-        let init_pos = self.current_pos;
+        if let Some(item) = self.parse_item()? {
+            return Ok(Some(Input::Item(item)));
+        }
 
-        // Skip "var" keyword
-        self.step(1);
+        Ok(self.parse_node()?.map(Input::Stmt))
+    }
 
-        if !self.equals_type(TokenType::Keyword) || self.is_forbidden_keyword() {
-            println!("Invalid variable name");
-            self.current_pos = init_pos;
+    fn parse_node(&mut self) -> ParseResult<Stmt> {
+        self.skip_newlines();
 
-            return None;
+        // 9 possibilities :
+        //  - destructuring declaration
+        //  - variable declaration
+        //  - condition
+        //  - loop
+        //  - match
+        //  - block
+        //  - statement (return, break...)
+        //  - assignment
+        //  - expression (function call, operation...)
+        if let Some(destructure) = self.parse_destructure()? {
+            return Ok(Some(Stmt::Destructure(destructure)));
         }
 
-        let var_name = self.peek(0).unwrap().content.clone();
+        if let Some(var_decl) = self.parse_var_decl()? {
+            return Ok(Some(Stmt::Variable(var_decl)));
+        }
 
-        // Skip variable name
-        self.step(1);
+        if let Some(block) = self.parse_block()? {
+            return Ok(Some(Stmt::Block(block)));
+        }
 
-        if !self.equals_content("::") {
-            self.expected("::");
-            self.current_pos = init_pos;
+        if let Some(condition) = self.parse_if()? {
+            return Ok(Some(Stmt::If(condition)));
+        }
 
-            return None;
+        if let Some(loop_node) = self.parse_loop()? {
+            return Ok(Some(Stmt::Loop(loop_node)));
         }
 
-        // Skip "::" symbol
-        self.step(1);
+        if let Some(match_node) = self.parse_match()? {
+            return Ok(Some(Stmt::Match(match_node)));
+        }
 
-        if !self.equals_type(TokenType::Keyword) {
-            println!("Invalid variable type");
-            self.current_pos = init_pos;
+        if let Some(statement) = self.parse_statement()? {
+            return Ok(Some(Stmt::Statement(statement)));
+        }
 
-            return None;
+        match self.parse_expr()? {
+            Some(expr) => self.parse_assign(expr).map(Some),
+            None => Ok(None),
         }
+    }
+
+    // <target> = <value>, or a compound assignment such as <target> += <value>.
+    // Without an assignment operator, the target is an expression on its own
+    fn parse_assign(&mut self, target: Expr) -> Result<Stmt, ParseError> {
+        let operator = match self.peek(1).and_then(AssignOperator::from_token) {
+            Some(op) => op,
+            None => return Ok(Stmt::Expr(target)),
+        };
 
-        let var_type = match Type::from_token(self.peek(0).unwrap()) {
-            Some(t) => t,
-            None => {
-                println!("Invalid variable type");
-                self.current_pos = init_pos;
+        // Skip target and operator
+        self.step(2);
 
-                return None;
-            }
+        let value = self.parse_expr()?.ok_or_else(|| self.expected_expr())?;
+
+        Ok(Stmt::Assign(AssignNode {
+            span: target.span().to(value.span()),
+            target,
+            operator,
+            value,
+        }))
+    }
+
+    fn parse_expr(&mut self) -> ParseResult<Expr> {
+        self.nested(|parser| parser.parse_operation(0))
+    }
+
+    // Precedence climbing: only operators binding at least as tight as `min_precedence`
+    // are consumed, the others are left to the caller
+    fn parse_operation(&mut self, min_precedence: u8) -> ParseResult<Expr> {
+        let mut left = match self.parse_cast()? {
+            Some(node) => node,
+            None => return Ok(None),
         };
 
-        // Skip variable type
-        self.step(1);
+        loop {
+            let operator = match self.peek(1).and_then(BinaryOperator::from_token) {
+                Some(op) if op.precedence() >= min_precedence => op,
+                _ => break,
+            };
 
-        if !self.equals_content("=") {
-            self.expected("=");
-            self.current_pos = init_pos;
+            // Skip left operand and operator
+            self.step(2);
+            self.deepen()?;
 
-            return None;
-        }
+            let right = self.nested(|parser| parser.parse_operation(operator.precedence() + 1))?
+                .ok_or_else(|| self.expected_expr())?;
 
-        // Skip assign operator
-        self.step(1);
+            left = Expr::BinaryOp(BinaryOpNode {
+                operator,
+                span: left.span().to(right.span()),
+                left: Box::new(left),
+                right: Box::new(right),
+            });
+        }
 
-        let var_value = self.parse_expr();
-        return match var_value {
-            None => {
-                println!("Invalid variable value");
-                self.current_pos = init_pos;
+        Ok(Some(left))
+    }
 
-                None
-            }
-            Some(node) => Some(Box::new(VariableNode {
-                var_type,
-                name: var_name,
-                value: node,
-            }))
+    // 'as' binds tighter than binary operators and looser than unary ones
+    fn parse_cast(&mut self) -> ParseResult<Expr> {
+        let mut expr = match self.parse_unary()? {
+            Some(node) => node,
+            None => return Ok(None),
         };
-    }
 
-    fn parse_func_call(&mut self) -> Option<Node> {
-        if !self.equals_type(TokenType::Keyword) || self.is_forbidden_keyword() {
-            return None;
+        while self.peek(1).is_some_and(|token| token.is("as")) {
+            // Skip operand and "as" keyword
+            self.step(2);
+            self.deepen()?;
+
+            let target = self.parse_type()?;
+            expr = Expr::Cast(CastNode {
+                span: expr.span().to(self.current_token().span),
+                expr: Box::new(expr),
+                target,
+            });
         }
 
-        let init_pos = self.current_pos;
-        let func_name = self.peek(0).unwrap().content.clone();
+        Ok(Some(expr))
+    }
 
-        // Skip function name
+    fn parse_unary(&mut self) -> ParseResult<Expr> {
+        let operator = match self.peek(0).and_then(UnaryOperator::from_token) {
+            Some(op) => op,
+            None => return self.parse_postfix(),
+        };
+        let start = self.current_token().span;
+
+        // Skip operator
         self.step(1);
 
-        if !self.equals_content("(") {
-            self.current_pos = init_pos;
-            return None;
+        let operand = self.nested(Self::parse_unary)?.ok_or_else(|| self.expected_expr())?;
+        let span = self.span_from(start);
+
+        // Negative literals are folded, so that they are range checked as a whole
+        if let (UnaryOperator::Neg, Expr::Number(node)) = (operator, &operand) {
+            let value = match node.value {
+                Number::Integer { negative, magnitude } =>
+                    Number::Integer { negative: !negative, magnitude },
+                Number::Float(v) => Number::Float(-v),
+            };
+            return Ok(Some(Expr::Number(NumberNode { value, number_type: None, span })));
         }
 
-        // Skip opened parenthesis
-        self.step(1);
+        Ok(Some(Expr::UnaryOp(UnaryOpNode {
+            operator,
+            operand: Box::new(operand),
+            span,
+        })))
+    }
 
-        // Parse function arguments
-        let mut func_args = Vec::<Node>::new();
+    // Field accesses, method calls and indexing following a primary expression:
+    // <expr>.<field>.<method>(...)[<index>]...
+    fn parse_postfix(&mut self) -> ParseResult<Expr> {
+        let mut expr = match self.parse_primary()? {
+            Some(node) => node,
+            None => return Ok(None),
+        };
 
-        while self.peek(0).is_some() && !self.equals_content(")") {
-            match self.parse_expr() {
-                None => println!("Invalid function parameter '{}'",
-                                 self.peek(0).unwrap().content),
-                Some(arg) => func_args.push(arg)
+        loop {
+            if self.peek(1).is_some_and(|token| token.is(".") || token.is("[")) {
+                self.deepen()?;
             }
 
-            self.step(1);
-            if self.equals_content(",") {
+            if self.peek(1).is_some_and(|token| token.is(".")) {
+                // Skip expression and dot
+                self.step(2);
+
+                // Elements of a tuple, `t.0.1` being lexed as `t`, `.`, `0.1`
+                if self.equals_type(TokenType::Number) {
+                    let token = self.current_token();
+                    let mut start = token.span.start;
+
+                    for position in token.content.split('.') {
+                        if position.is_empty() || !position.bytes().all(|byte| byte.is_ascii_digit()) {
+                            return Err(self.error(ParseErrorKind::InvalidName, &["tuple position"]));
+                        }
+
+                        let span = Span::new(start, start + position.len());
+                        start = span.end + 1;
+                        expr = Expr::Field(FieldNode {
+                            span: expr.span().to(span),
+                            expr: Box::new(expr),
+                            field: position.to_string(),
+                        });
+                    }
+                    continue;
+                }
+
+                if !self.is_name() {
+                    return Err(self.error(ParseErrorKind::InvalidName, &["field or method name"]));
+                }
+
+                let name = self.current_token().content;
+                if self.peek(1).is_some_and(|token| token.is("(")) {
+                    // Skip method name
+                    self.step(1);
+                    let args = self.parse_list(|parser| {
+                        parser.with_struct_literals(true, Self::parse_expr)?.ok_or_else(|| parser.expected_expr())
+                    })?;
+
+                    expr = Expr::MethodCall(MethodCallNode {
+                        span: self.span_from(expr.span()),
+                        receiver: Box::new(expr),
+                        method: name,
+                        args,
+                        trait_name: None,
+                    });
+                } else {
+                    expr = Expr::Field(FieldNode {
+                        span: self.span_from(expr.span()),
+                        expr: Box::new(expr),
+                        field: name,
+                    });
+                }
+            } else if self.peek(1).is_some_and(|token| token.is("[")) {
+                // Skip expression and opened bracket
+                self.step(2);
+
+                let index = self.with_struct_literals(true, Self::parse_expr)?
+                    .ok_or_else(|| self.expected_expr())?;
                 self.step(1);
+                self.expect_content("]")?;
+
+                expr = Expr::Index(IndexNode {
+                    span: self.span_from(expr.span()),
+                    expr: Box::new(expr),
+                    index: Box::new(index),
+                });
+            } else {
+                return Ok(Some(expr));
             }
         }
-
-        Some(Box::new(FunctionCallNode {
-            name: func_name,
-            args: func_args,
-        }))
     }
 
-    fn parse_var_call(&mut self) -> Option<Node> {
-        if !self.equals_type(TokenType::Keyword) || self.is_forbidden_keyword() {
-            return None;
-        }
+    fn parse_primary(&mut self) -> ParseResult<Expr> {
+        // 9 possibilities :
+        //  - parenthesized expression or tuple
+        //  - array literal
+        //  - if expression
+        //  - match expression
+        //  - fixed value (boolean, integer...)
+        //  - enum variant
+        //  - struct literal
+        //  - variable call
+        //  - function call
+        if self.equals_content("(") {
+            let start = self.current_token().span;
 
-        Some(Box::new(VariableCallNode {
-            name: self.peek(0).unwrap().content.clone()
-        }))
-    }
+            // Skip opened parenthesis
+            self.step(1);
 
-    fn parse_block(&mut self) -> Option<Node> {
-        if !self.equals_content("{") {
-            return None;
-        }
+            let mut elements = Vec::new();
+            loop {
+                elements.push(self.with_struct_literals(true, Self::parse_expr)?
+                    .ok_or_else(|| self.expected_expr())?);
+                self.step(1);
 
-        self.step(1);
+                if self.equals_content(")") {
+                    break;
+                } else if !self.equals_content(",") {
+                    return Err(self.error(ParseErrorKind::UnexpectedToken, &["','", "')'"]));
+                }
 
-        let mut nodes = Vec::<Node>::new();
-        while !self.equals_content("}") {
-            match self.parse_node() {
-                None => break,
-                Some(node) => nodes.push(node)
+                // Skip comma
+                self.step(1);
             }
 
-            if !self.step(1) {
-                println!("Could not find the end of the block!");
-                break;
+            if elements.len() == 1 {
+                return Ok(elements.pop());
             }
-        }
 
-        Some(Box::new(BlockNode { nodes }))
-    }
+            return Ok(Some(Expr::Tuple(TupleNode { elements, span: self.span_from(start) })));
+        }
 
-    fn parse_statement(&mut self) -> Option<Node> {
-        if !self.equals_type(TokenType::Keyword) {
-            return None;
+        if let Some(array) = self.parse_array()? {
+            return Ok(Some(Expr::Array(array)));
         }
 
-        let stat_type = match Statement::from_token(self.peek(0).unwrap()) {
-            Some(s) => s,
-            None => return None,
-        };
+        if let Some(condition) = self.parse_if()? {
+            return Ok(Some(Expr::If(Box::new(condition))));
+        }
 
-        let mut stat_value = None;
-        if stat_type == Statement::Return {
-            self.step(1);
+        if let Some(match_node) = self.parse_match()? {
+            return Ok(Some(Expr::Match(Box::new(match_node))));
+        }
 
-            stat_value = match self.parse_expr() {
-                None => {
-                    println!("Invalid return value");
-                    None
-                }
-                Some(node) => Some(node)
-            };
+        let value = self.parse_value()?;
+        if value.is_some() {
+            return Ok(value);
         }
 
-        Some(Box::new(StatementNode { stat_type, value: stat_value }))
-    }
+        if let Some(variant) = self.parse_variant_call()? {
+            return Ok(Some(Expr::VariantCall(variant)));
+        }
 
-    fn parse_func_decl(&mut self) -> Option<Node> {
-        if !self.equals_content("fcn") {
-            return None;
+        if let Some(literal) = self.parse_struct_literal()? {
+            return Ok(Some(Expr::StructLiteral(literal)));
         }
-        self.step(1);
 
-        if !self.equals_type(TokenType::Keyword) {
-            return None;
+        if let Some(func_call) = self.parse_func_call()? {
+            return Ok(Some(Expr::FunctionCall(func_call)));
         }
 
-        let func_name = self.peek(0).unwrap().content.clone();
-        self.step(1);
+        Ok(self.parse_var_call().map(Expr::VariableCall))
+    }
 
-        if !self.equals_content("(") {
-            self.expected("(");
-            return None;
+    // [<value>, ...] or [<value>; <length>], the values can span several lines
+    fn parse_array(&mut self) -> ParseResult<ArrayNode> {
+        if !self.equals_content("[") {
+            return Ok(None);
         }
-        self.step(1);
+        let start = self.current_token().span;
 
-        // Parse function arguments
-        let mut func_args = Vec::<FunctionArgNode>::new();
+        // Skip opened bracket
+        self.step(1);
+        self.skip_newlines();
 
-        while self.peek(0).is_some() && !self.equals_content(")") {
-            if !self.equals_type(TokenType::Keyword) {
-                println!("Invalid argument name");
-                return None;
-            }
+        let mut values = Vec::new();
 
-            let arg_name = self.peek(0).unwrap().content.clone();
+        while !self.equals_content("]") {
+            let value = self.with_struct_literals(true, Self::parse_expr)?
+                .ok_or_else(|| self.expected_expr())?;
             self.step(1);
 
-            if !self.equals_content("::") {
-                self.expected("::");
-                return None;
+            if values.is_empty() && self.equals_content(";") {
+                // Skip semicolon
+                self.step(1);
+                let length = self.parse_length()?;
+                self.step(1);
+                self.skip_newlines();
+                self.expect_content("]")?;
+
+                return Ok(Some(ArrayNode {
+                    elements: ArrayElements::Repeat { value: Box::new(value), length },
+                    array_type: None,
+                    span: self.span_from(start),
+                }));
             }
-            self.step(1);
-
-            let arg_type = match Type::from_token(self.peek(0).unwrap()) {
-                Some(t) => t,
-                None => {
-                    println!("Invalid argument type");
-                    return None;
-                }
-            };
 
-            func_args.push(FunctionArgNode {
-                var_type: arg_type,
-                name: arg_name,
-            });
+            values.push(value);
 
-            self.step(1);
+            self.skip_newlines();
             if self.equals_content(",") {
                 self.step(1);
+                self.skip_newlines();
+            } else if !self.equals_content("]") {
+                return Err(self.error(ParseErrorKind::UnexpectedToken, &["','", "']'"]));
             }
-        };
+        }
 
-        // Skip last parenthesis
-        self.step(1);
+        Ok(Some(ArrayNode {
+            elements: ArrayElements::Values(values),
+            array_type: None,
+            span: self.span_from(start),
+        }))
+    }
 
-        if !self.equals_content("->") {
-            self.expected("->");
-            return None;
+    fn parse_value(&mut self) -> ParseResult<Expr> {
+        if self.equals_type(TokenType::Keyword) {
+            return Ok(self.parse_bool());
+        } else if self.equals_type(TokenType::Number) {
+            return self.parse_number().map(Some);
+        } else if self.equals_type(TokenType::String) {
+            return Ok(self.parse_string());
+        } else if self.equals_type(TokenType::Char) {
+            return Ok(self.parse_char());
         }
+        Ok(None)
+    }
 
-        // Skip arrow
-        self.step(1);
+    fn parse_bool(&mut self) -> Option<Expr> {
+        let span = self.current_token().span;
 
-        let return_type = match Type::from_token(self.peek(0).unwrap()) {
-            Some(t) => t,
-            None => {
-                println!("Invalid return type");
-                return None;
-            }
-        };
+        if self.equals_content("True") {
+            return Some(Expr::Bool(BoolNode { value: true, span }));
+        } else if self.equals_content("False") {
+            return Some(Expr::Bool(BoolNode { value: false, span }));
+        }
+        None
+    }
 
-        // Skip return type
-        self.step(1);
+    fn parse_number(&mut self) -> Result<Expr, ParseError> {
+        let token = self.current_token();
+        let digits = token.content.replace('_', "");
 
-        let block = match self.parse_block() {
-            Some(b) => b,
-            None => {
-                println!("Invalid block");
-                return None;
-            }
+        let radix = match digits.get(..2) {
+            Some("0x") => Some(16),
+            Some("0b") => Some(2),
+            Some("0o") => Some(8),
+            _ => None,
         };
 
-        Some(Box::new(FunctionNode {
-            name: func_name,
-            args: func_args,
-            return_type,
-            block,
-        }))
+        let integer = |parsed: Result<u128, ParseIntError>| match parsed {
+            Ok(magnitude) => Ok(Number::Integer { negative: false, magnitude }),
+            Err(err) if *err.kind() == IntErrorKind::PosOverflow => Err(ParseErrorKind::NumberTooLarge(u128::BITS)),
+            Err(_) => Err(ParseErrorKind::InvalidNumber),
+        };
+        let value = match radix {
+            Some(radix) => integer(u128::from_str_radix(&digits[2..], radix)),
+            None if digits.contains(['.', 'e', 'E']) =>
+                digits.parse::<f64>().map(Number::Float).map_err(|_| ParseErrorKind::InvalidNumber),
+            None => integer(digits.parse::<u128>()),
+        };
+
+        match value {
+            Ok(value) => Ok(Expr::Number(NumberNode { value, number_type: None, span: token.span })),
+            Err(kind @ ParseErrorKind::NumberTooLarge(_)) => Err(self.error(kind, &["smaller number"])),
+            Err(kind) => Err(self.error(kind, &["number"])),
+        }
+    }
+
+    fn parse_string(&mut self) -> Option<Expr> {
+        let token = self.current_token();
+        Some(Expr::String(StringNode { value: token.content, span: token.span }))
+    }
+
+    fn parse_char(&mut self) -> Option<Expr> {
+        let token = self.current_token();
+        let value = token.content.chars().next()?;
+        Some(Expr::Char(CharNode { value, span: token.span }))
+    }
+
+    // Any name can be a struct type, the checker makes sure it is declared
+    fn parse_type(&mut self) -> Result<Type, ParseError> {
+        if let Some(self_type) = self.self_type.as_ref().filter(|_| self.equals_content("Self")) {
+            return Ok(self_type.clone());
+        }
+
+        if let Some(primitive) = self.peek(0).and_then(Type::from_token) {
+            return Ok(primitive);
+        }
+
+        // [<type>; <length>]
+        if self.equals_content("[") {
+            self.step(1);
+            let element_type = self.nested(Self::parse_type)?;
+            self.step(1);
+            self.expect_content(";")?;
+            self.step(1);
+            let length = self.parse_length()?;
+            self.step(1);
+            self.expect_content("]")?;
+
+            return Ok(Type::Array(Box::new(element_type), length));
+        }
+
+        // (<type>, <type>, ...), a single type in parentheses being that type
+        if self.equals_content("(") {
+            let mut types = self.parse_list(|parser| parser.nested(Self::parse_type))?;
+
+            return match types.len() {
+                0 => Err(self.error(ParseErrorKind::InvalidType, &["type"])),
+                1 => Ok(types.remove(0)),
+                _ => Ok(Type::Tuple(types)),
+            };
+        }
+
+        // List<<type>>
+        if self.equals_content("List") {
+            self.step(1);
+            self.expect_content("<")?;
+            self.step(1);
+            let element_type = self.nested(Self::parse_type)?;
+            self.step(1);
+            self.expect_closing_angle()?;
+
+            return Ok(Type::List(Box::new(element_type)));
+        }
+
+        if self.is_name() {
+            let name = self.current_token().content;
+            if self.type_params.contains(&name) {
+                return Ok(Type::Param(name));
+            }
+
+            // <name><<type>, ...>
+            let mut args = Vec::new();
+            if self.peek(1).is_some_and(|token| token.is("<")) {
+                // Skip name and '<'
+                self.step(2);
+
+                loop {
+                    args.push(self.nested(Self::parse_type)?);
+                    self.step(1);
+
+                    if !self.equals_content(",") {
+                        break;
+                    }
+                    self.step(1);
+                }
+                self.expect_closing_angle()?;
+            }
+
+            return Ok(Type::Named(name, args));
+        }
+
+        Err(self.error(ParseErrorKind::InvalidType, &["type"]))
+    }
+
+    // <<name>, ...> following the name of a generic declaration, which is
+    // where the parsing ends. Empty if the declaration is not generic. If
+    // `bounded`, a parameter can be followed by `: <trait> + ...`, the bounds
+    // being kept in `self.bounds`
+    fn parse_type_params(&mut self, bounded: bool) -> Result<Vec<String>, ParseError> {
+        let mut params = Vec::new();
+        if !self.peek(1).is_some_and(|token| token.is("<")) {
+            return Ok(params);
+        }
+
+        // Skip name and '<'
+        self.step(2);
+
+        loop {
+            if !self.is_name() {
+                return Err(self.error(ParseErrorKind::InvalidName, &["type parameter name"]));
+            }
+            let param = self.current_token().content;
+            self.step(1);
+
+            if bounded && self.equals_content(":") {
+                loop {
+                    self.step(1);
+                    if !self.is_name() {
+                        return Err(self.error(ParseErrorKind::InvalidName, &["trait name"]));
+                    }
+                    let token = self.current_token();
+                    self.bounds.push(BoundNode { param: param.clone(), trait_name: token.content, span: token.span });
+                    self.step(1);
+
+                    if !self.equals_content("+") {
+                        break;
+                    }
+                }
+            }
+            params.push(param);
+
+            if !self.equals_content(",") {
+                break;
+            }
+            self.step(1);
+        }
+        self.expect_closing_angle()?;
+
+        Ok(params)
+    }
+
+    // Length of an array, which must be an integer literal
+    fn parse_length(&self) -> Result<usize, ParseError> {
+        let token = self.current_token();
+        if token.token_type == TokenType::Number {
+            match token.content.replace('_', "").parse::<usize>() {
+                Ok(length) => return Ok(length),
+                Err(err) if *err.kind() == IntErrorKind::PosOverflow =>
+                    return Err(self.error(ParseErrorKind::NumberTooLarge(usize::BITS), &["smaller array length"])),
+                Err(_) => {}
+            }
+        }
+        Err(self.error(ParseErrorKind::InvalidNumber, &["array length"]))
+    }
+
+    // The closing '>' of a type can be glued to the next symbols by the
+    // lexer, as in List<List<Int64>>, in which case the token is split
+    fn expect_closing_angle(&mut self) -> Result<(), ParseError> {
+        let token = self.current_token();
+        if token.token_type == TokenType::Operator && token.content.len() > 1 && token.content.starts_with('>') {
+            let split = token.span.start + 1;
+            self.tokens[self.current_pos] = Token {
+                token_type: TokenType::Operator,
+                content: ">".to_string(),
+                span: Span::new(token.span.start, split),
+            };
+            let rest = Token {
+                token_type: TokenType::Operator,
+                content: token.content[1..].to_string(),
+                span: Span::new(split, token.span.end),
+            };
+            self.tokens.insert(self.current_pos + 1, rest);
+        }
+        self.expect_content(">")
+    }
+
+    fn parse_var_decl(&mut self) -> ParseResult<VariableNode> {
+        let kind = match self.peek(0).and_then(VariableKind::from_token) {
+            Some(kind) => kind,
+            None => return Ok(None),
+        };
+        let start = self.current_token().span;
+        let doc = self.parse_doc();
+
+        // Skip "var", "let" or "const" keyword
+        self.step(1);
+
+        if !self.is_name() {
+            return Err(self.error(ParseErrorKind::InvalidName, &["variable name"]));
+        }
+
+        let var_name = self.peek(0).unwrap().content.clone();
+
+        // Skip variable name
+        self.step(1);
+
+        // The type can be left out, to be inferred from the value
+        let mut var_type = None;
+        if self.equals_content("::") {
+            // Skip "::" symbol
+            self.step(1);
+
+            var_type = Some(self.parse_type()?);
+
+            // Skip variable type
+            self.step(1);
+        } else if !self.equals_content("=") {
+            return Err(self.error(ParseErrorKind::UnexpectedToken, &["'::'", "'='"]));
+        }
+
+        self.expect_content("=")?;
+
+        // Skip assign operator
+        self.step(1);
+
+        let var_value = self.parse_expr()?.ok_or_else(|| self.expected_expr())?;
+
+        Ok(Some(VariableNode {
+            kind,
+            var_type,
+            name: var_name,
+            value: var_value,
+            doc,
+            span: self.span_from(start),
+        }))
+    }
+
+    // <kind> (<pattern>, ...) = <value>, with an optional type like a variable declaration
+    fn parse_destructure(&mut self) -> ParseResult<DestructureNode> {
+        let kind = match self.peek(0).and_then(VariableKind::from_token) {
+            Some(kind) if self.peek(1).is_some_and(|token| token.is("(")) => kind,
+            _ => return Ok(None),
+        };
+        let start = self.current_token().span;
+
+        // Skip "var", "let" or "const" keyword
+        self.step(1);
+
+        let pattern = self.parse_pattern()?;
+
+        // Skip pattern
+        self.step(1);
+
+        let mut var_type = None;
+        if self.equals_content("::") {
+            // Skip "::" symbol
+            self.step(1);
+
+            var_type = Some(self.parse_type()?);
+
+            // Skip variable type
+            self.step(1);
+        } else if !self.equals_content("=") {
+            return Err(self.error(ParseErrorKind::UnexpectedToken, &["'::'", "'='"]));
+        }
+
+        self.expect_content("=")?;
+
+        // Skip assign operator
+        self.step(1);
+
+        let value = self.parse_expr()?.ok_or_else(|| self.expected_expr())?;
+
+        Ok(Some(DestructureNode { kind, pattern, var_type, value, span: self.span_from(start) }))
+    }
+
+    fn parse_func_call(&mut self) -> ParseResult<FunctionCallNode> {
+        if !self.is_name() {
+            return Ok(None);
+        }
+
+        let init_pos = self.current_pos;
+        let start = self.current_token().span;
+        let func_name = self.peek(0).unwrap().content.clone();
+
+        // Skip function name
+        self.step(1);
+
+        if !self.equals_content("(") {
+            self.current_pos = init_pos;
+            return Ok(None);
+        }
+
+        // Skip opened parenthesis
+        self.step(1);
+
+        // Parse function arguments
+        let mut func_args = Vec::<Expr>::new();
+
+        while !self.equals_content(")") {
+            let arg = self.with_struct_literals(true, Self::parse_expr)?
+                .ok_or_else(|| self.expected_expr())?;
+            func_args.push(arg);
+
+            self.step(1);
+            if self.equals_content(",") {
+                self.step(1);
+            } else if !self.equals_content(")") {
+                return Err(self.error(ParseErrorKind::UnexpectedToken, &["','", "')'"]));
+            }
+        }
+
+        Ok(Some(FunctionCallNode {
+            name: func_name,
+            args: func_args,
+            type_args: Vec::new(),
+            span: self.span_from(start),
+        }))
+    }
+
+    fn parse_var_call(&mut self) -> Option<VariableCallNode> {
+        if !self.is_name() {
+            return None;
+        }
+
+        let token = self.current_token();
+        Some(VariableCallNode { name: token.content, span: token.span })
+    }
+
+    fn parse_block(&mut self) -> ParseResult<BlockNode> {
+        if !self.equals_content("{") {
+            return Ok(None);
+        }
+        let start = self.current_token().span;
+
+        self.step(1);
+
+        let mut nodes = Vec::<Stmt>::new();
+        loop {
+            self.skip_newlines();
+
+            if self.equals_content("}") {
+                break;
+            } else if self.equals_type(TokenType::Eof) {
+                return Err(self.error(ParseErrorKind::UnclosedBlock, &["'}'"]));
+            }
+
+            // A malformed node is recorded and skipped, so that the errors
+            // of the following ones are reported as well
+            match self.nested(|parser| parser.with_struct_literals(true, Self::parse_node)) {
+                Ok(Some(node)) => nodes.push(node),
+                Ok(None) => {
+                    let err = self.error(ParseErrorKind::UnexpectedToken, &["statement"]);
+                    self.errors.push(err);
+                    self.synchronize();
+                    continue;
+                }
+                // Every enclosing block would be as deep, the error is only reported once
+                Err(err) if err.kind == ParseErrorKind::TooDeeplyNested => return Err(err),
+                Err(err) => {
+                    self.errors.push(err);
+                    self.synchronize();
+                    continue;
+                }
+            }
+
+            // Skip the last token of the node
+            self.step(1);
+
+            if !self.equals_content("\n") && !self.equals_content("}") {
+                let err = self.error(ParseErrorKind::UnexpectedToken, &["new line", "'}'"]);
+                self.errors.push(err);
+                self.synchronize();
+            }
+        }
+
+        Ok(Some(BlockNode { nodes, span: self.span_from(start) }))
+    }
+
+    // if <condition> { ... } else if <condition> { ... } else { ... }
+    // 'else' must be on the same line as the closing brace before it
+    fn parse_if(&mut self) -> ParseResult<IfNode> {
+        if !self.equals_content("if") {
+            return Ok(None);
+        }
+        let start = self.current_token().span;
+
+        // Skip "if" keyword
+        self.step(1);
+
+        let condition = self.parse_head_expr()?.ok_or_else(|| self.expected_expr())?;
+
+        // Skip condition
+        self.step(1);
+
+        let then_block = self.parse_block()?
+            .ok_or_else(|| self.error(ParseErrorKind::UnexpectedToken, &["'{'"]))?;
+
+        let mut else_branch = None;
+        if self.peek(1).is_some_and(|token| token.is("else")) {
+            // Skip closing brace and "else" keyword
+            self.step(2);
+
+            if let Some(else_if) = self.nested(Self::parse_if)? {
+                else_branch = Some(ElseBranch::If(Box::new(else_if)));
+            } else {
+                let block = self.parse_block()?
+                    .ok_or_else(|| self.error(ParseErrorKind::UnexpectedToken, &["'{'", "'if'"]))?;
+                else_branch = Some(ElseBranch::Block(block));
+            }
+        }
+
+        Ok(Some(IfNode { condition, then_block, else_branch, span: self.span_from(start) }))
+    }
+
+    // Label name of a 'name token, without the quote
+    fn parse_label(&self) -> Option<String> {
+        match self.peek(0) {
+            Some(token) if token.token_type == TokenType::Label =>
+                Some(token.content.trim_start_matches('\'').to_string()),
+            _ => None,
+        }
+    }
+
+    fn parse_loop(&mut self) -> ParseResult<LoopNode> {
+        let start = self.current_token().span;
+
+        // Optional label: 'name: while ...
+        let label = self.parse_label();
+        if label.is_some() {
+            // Skip label
+            self.step(1);
+            self.expect_content(":")?;
+
+            // Skip colon
+            self.step(1);
+        }
+
+        let kind = if self.equals_content("loop") {
+            self.step(1);
+            LoopKind::Infinite
+        } else if self.equals_content("while") {
+            self.step(1);
+
+            let condition = self.parse_head_expr()?.ok_or_else(|| self.expected_expr())?;
+            self.step(1);
+            LoopKind::While(condition)
+        } else if self.equals_content("for") {
+            self.step(1);
+            self.parse_for()?
+        } else if label.is_some() {
+            return Err(self.error(ParseErrorKind::UnexpectedToken, &["'loop'", "'while'", "'for'"]));
+        } else {
+            return Ok(None);
+        };
+
+        let block = self.parse_block()?
+            .ok_or_else(|| self.error(ParseErrorKind::UnexpectedToken, &["'{'"]))?;
+
+        Ok(Some(LoopNode { kind, label, block, span: self.span_from(start) }))
+    }
+
+    // <var_name> in <start>..<end>, leaves the current token on the block
+    fn parse_for(&mut self) -> Result<LoopKind, ParseError> {
+        if !self.is_name() {
+            return Err(self.error(ParseErrorKind::InvalidName, &["variable name"]));
+        }
+
+        let var_name = self.peek(0).unwrap().content.clone();
+
+        // Skip variable name
+        self.step(1);
+        self.expect_content("in")?;
+
+        // Skip "in" keyword
+        self.step(1);
+
+        let start = self.parse_head_expr()?.ok_or_else(|| self.expected_expr())?;
+        self.step(1);
+        self.expect_content("..")?;
+
+        // Skip range symbol
+        self.step(1);
+
+        let end = self.parse_head_expr()?.ok_or_else(|| self.expected_expr())?;
+        self.step(1);
+
+        Ok(LoopKind::For { var_name, start, end })
+    }
+
+    fn parse_statement(&mut self) -> ParseResult<StatementNode> {
+        if !self.equals_type(TokenType::Keyword) {
+            return Ok(None);
+        }
+
+        let stat_type = match Statement::from_token(self.peek(0).unwrap()) {
+            Some(s) => s,
+            None => return Ok(None),
+        };
+        let start = self.current_token().span;
+
+        let mut stat_value = None;
+        let mut label = None;
+        if stat_type == Statement::Return {
+            self.step(1);
+            stat_value = Some(self.parse_expr()?.ok_or_else(|| self.expected_expr())?);
+        } else if self.peek(1).is_some_and(|token| token.token_type == TokenType::Label) {
+            // Skip "break" or "continue" keyword
+            self.step(1);
+            label = self.parse_label();
+        }
+
+        Ok(Some(StatementNode { stat_type, value: stat_value, label, span: self.span_from(start) }))
+    }
+
+    fn parse_func_decl(&mut self) -> ParseResult<FunctionNode> {
+        if !self.equals_content("fcn") {
+            return Ok(None);
+        }
+        let start = self.current_token().span;
+        let doc = self.parse_doc();
+        self.step(1);
+
+        if !self.is_name() {
+            return Err(self.error(ParseErrorKind::InvalidName, &["function name"]));
+        }
+
+        let func_name = self.peek(0).unwrap().content.clone();
+        self.type_params = self.parse_type_params(true)?;
+        self.step(1);
+        let (func_args, return_type) = self.parse_signature()?;
+
+        // Skip return type
+        self.step(1);
+
+        let block = self.parse_block()?
+            .ok_or_else(|| self.error(ParseErrorKind::UnexpectedToken, &["'{'"]))?;
+
+        Ok(Some(FunctionNode {
+            name: func_name,
+            type_params: mem::take(&mut self.type_params),
+            bounds: mem::take(&mut self.bounds),
+            args: func_args,
+            return_type,
+            block,
+            doc,
+            span: self.span_from(start),
+        }))
+    }
+
+    // (<arg> :: <type>, ...) -> <type>, from the opened parenthesis to the return type
+    fn parse_signature(&mut self) -> Result<(Vec<FunctionArgNode>, Type), ParseError> {
+        self.expect_content("(")?;
+        self.step(1);
+
+        let mut func_args = Vec::<FunctionArgNode>::new();
+
+        while !self.equals_content(")") {
+            if !self.is_name() {
+                return Err(self.error(ParseErrorKind::InvalidName, &["argument name"]));
+            }
+
+            let arg_start = self.current_token().span;
+            let arg_name = self.peek(0).unwrap().content.clone();
+
+            let arg_type = match &self.self_type {
+                // The receiver of a method, of the type `Self` designates
+                Some(self_type) if func_args.is_empty() && arg_name == "self"
+                    && !self.peek(1).is_some_and(|token| token.is("::")) => self_type.clone(),
+                _ => {
+                    self.step(1);
+                    self.expect_content("::")?;
+                    self.step(1);
+                    self.parse_type()?
+                }
+            };
+
+            func_args.push(FunctionArgNode {
+                var_type: arg_type,
+                name: arg_name,
+                span: self.span_from(arg_start),
+            });
+
+            self.step(1);
+            if self.equals_content(",") {
+                self.step(1);
+            } else if !self.equals_content(")") {
+                return Err(self.error(ParseErrorKind::UnexpectedToken, &["','", "')'"]));
+            }
+        };
+
+        // Skip last parenthesis
+        self.step(1);
+        self.expect_content("->")?;
+
+        // Skip arrow
+        self.step(1);
+
+        let return_type = self.parse_type()?;
+
+        Ok((func_args, return_type))
+    }
+
+    // trait <name> { <method signature> ... }
+    fn parse_trait_decl(&mut self) -> ParseResult<TraitNode> {
+        if !self.equals_content("trait") {
+            return Ok(None);
+        }
+        let start = self.current_token().span;
+        let doc = self.parse_doc();
+
+        // Skip "trait" keyword
+        self.step(1);
+
+        if !self.is_name() {
+            return Err(self.error(ParseErrorKind::InvalidName, &["trait name"]));
+        }
+
+        let trait_name = self.peek(0).unwrap().content.clone();
+        self.step(1);
+        self.expect_content("{")?;
+        self.step(1);
+
+        // Stands for the type implementing the trait
+        self.self_type = Some(Type::Param("Self".to_string()));
+
+        let mut methods = Vec::new();
+        while !self.at_entries_end() {
+            methods.push(self.parse_method_decl()?);
+            self.step(1);
+            self.expect_entry_separator()?;
+        }
+
+        self.self_type = None;
+
+        Ok(Some(TraitNode { name: trait_name, methods, doc, span: self.span_from(start) }))
+    }
+
+    // fcn <name>(self, <arg> :: <type>, ...) -> <type>, without the body
+    fn parse_method_decl(&mut self) -> Result<MethodDeclNode, ParseError> {
+        self.expect_content("fcn")?;
+        let start = self.current_token().span;
+        let doc = self.parse_doc();
+        self.step(1);
+
+        if !self.is_name() {
+            return Err(self.error(ParseErrorKind::InvalidName, &["method name"]));
+        }
+
+        let name = self.peek(0).unwrap().content.clone();
+        self.step(1);
+
+        let (args, return_type) = self.parse_signature()?;
+
+        Ok(MethodDeclNode { name, args, return_type, doc, span: self.span_from(start) })
+    }
+
+    // impl<<param>, ...> <trait> for <type> { <method> ... }
+    fn parse_impl(&mut self) -> ParseResult<ImplNode> {
+        if !self.equals_content("impl") {
+            return Ok(None);
+        }
+        let start = self.current_token().span;
+        let doc = self.parse_doc();
+
+        self.type_params = self.parse_type_params(true)?;
+
+        // Skip "impl" keyword or the type parameters
+        self.step(1);
+
+        if !self.is_name() {
+            return Err(self.error(ParseErrorKind::InvalidName, &["trait name"]));
+        }
+
+        let trait_name = self.peek(0).unwrap().content.clone();
+        self.step(1);
+        self.expect_content("for")?;
+        self.step(1);
+
+        let target = self.parse_type()?;
+        self.step(1);
+        self.expect_content("{")?;
+        self.step(1);
+
+        self.self_type = Some(target.clone());
+
+        let mut methods = Vec::new();
+        while !self.at_entries_end() {
+            let method = self.parse_method_decl()?;
+
+            // Skip return type
+            self.step(1);
+
+            let block = self.parse_block()?
+                .ok_or_else(|| self.error(ParseErrorKind::UnexpectedToken, &["'{'"]))?;
+
+            // Methods are generic over the type parameters of the impl
+            methods.push(FunctionNode {
+                name: method.name,
+                type_params: self.type_params.clone(),
+                bounds: self.bounds.clone(),
+                args: method.args,
+                return_type: method.return_type,
+                block,
+                doc: method.doc,
+                span: self.span_from(method.span),
+            });
+
+            self.step(1);
+            self.expect_entry_separator()?;
+        }
+
+        self.self_type = None;
+
+        Ok(Some(ImplNode {
+            type_params: mem::take(&mut self.type_params),
+            bounds: mem::take(&mut self.bounds),
+            trait_name,
+            target,
+            methods,
+            doc,
+            span: self.span_from(start),
+        }))
+    }
+
+    // struct <name> { <field> :: <type>, ... }
+    fn parse_struct_decl(&mut self) -> ParseResult<StructNode> {
+        if !self.equals_content("struct") {
+            return Ok(None);
+        }
+        let start = self.current_token().span;
+        let doc = self.parse_doc();
+
+        // Skip "struct" keyword
+        self.step(1);
+
+        if !self.is_name() {
+            return Err(self.error(ParseErrorKind::InvalidName, &["struct name"]));
+        }
+
+        let struct_name = self.peek(0).unwrap().content.clone();
+        self.type_params = self.parse_type_params(false)?;
+        self.step(1);
+        self.expect_content("{")?;
+        self.step(1);
+
+        let mut fields = Vec::<StructFieldNode>::new();
+
+        while !self.at_entries_end() {
+            if !self.is_name() {
+                return Err(self.error(ParseErrorKind::InvalidName, &["field name"]));
+            }
+
+            let field_start = self.current_token().span;
+            let doc = self.parse_doc();
+            let field_name = self.peek(0).unwrap().content.clone();
+            self.step(1);
+            self.expect_content("::")?;
+            self.step(1);
+
+            let field_type = self.parse_type()?;
+
+            fields.push(StructFieldNode {
+                field_type,
+                name: field_name,
+                doc,
+                span: self.span_from(field_start),
+            });
+
+            self.step(1);
+            self.expect_entry_separator()?;
+        }
+
+        Ok(Some(StructNode {
+            name: struct_name,
+            type_params: mem::take(&mut self.type_params),
+            fields,
+            doc,
+            span: self.span_from(start),
+        }))
+    }
+
+    // <name> { <field>: <value>, ... }
+    fn parse_struct_literal(&mut self) -> ParseResult<StructLiteralNode> {
+        if !self.struct_literals || !self.is_name() || !self.peek(1).is_some_and(|token| token.is("{")) {
+            return Ok(None);
+        }
+        let start = self.current_token().span;
+        let struct_name = self.peek(0).unwrap().content.clone();
+
+        // Skip struct name and opened brace
+        self.step(2);
+
+        let mut fields = Vec::<FieldValueNode>::new();
+
+        while !self.at_entries_end() {
+            if !self.is_name() {
+                return Err(self.error(ParseErrorKind::InvalidName, &["field name"]));
+            }
+
+            let field_start = self.current_token().span;
+            let field_name = self.peek(0).unwrap().content.clone();
+            self.step(1);
+            self.expect_content(":")?;
+            self.step(1);
+
+            let value = self.parse_expr()?.ok_or_else(|| self.expected_expr())?;
+
+            fields.push(FieldValueNode {
+                name: field_name,
+                value,
+                span: self.span_from(field_start),
+            });
+
+            self.step(1);
+            self.expect_entry_separator()?;
+        }
+
+        Ok(Some(StructLiteralNode {
+            name: struct_name,
+            fields,
+            type_args: Vec::new(),
+            span: self.span_from(start),
+        }))
+    }
+
+    // Entries between braces (fields, variants or arms) can span several
+    // lines, the closing brace ends them
+    fn at_entries_end(&mut self) -> bool {
+        self.skip_newlines();
+        self.equals_content("}")
+    }
+
+    // Entries are separated by commas or new lines
+    fn expect_entry_separator(&mut self) -> Result<(), ParseError> {
+        if self.equals_content(",") {
+            self.step(1);
+        } else if !self.equals_content("\n") && !self.equals_content("}") {
+            return Err(self.error(ParseErrorKind::UnexpectedToken, &["','", "new line", "'}'"]));
+        }
+        Ok(())
+    }
+
+    // (<entry>, ...), from the opened parenthesis to the closing one
+    fn parse_list<T>(&mut self, parse_one: fn(&mut Self) -> Result<T, ParseError>)
+                     -> Result<Vec<T>, ParseError> {
+        // Skip opened parenthesis
+        self.step(1);
+
+        let mut entries = Vec::new();
+
+        while !self.equals_content(")") {
+            entries.push(parse_one(self)?);
+
+            self.step(1);
+            if self.equals_content(",") {
+                self.step(1);
+            } else if !self.equals_content(")") {
+                return Err(self.error(ParseErrorKind::UnexpectedToken, &["','", "')'"]));
+            }
+        }
+
+        Ok(entries)
+    }
+
+    // enum <name> { <variant>(<type>, ...), ... }
+    fn parse_enum_decl(&mut self) -> ParseResult<EnumNode> {
+        if !self.equals_content("enum") {
+            return Ok(None);
+        }
+        let start = self.current_token().span;
+        let doc = self.parse_doc();
+
+        // Skip "enum" keyword
+        self.step(1);
+
+        if !self.is_name() {
+            return Err(self.error(ParseErrorKind::InvalidName, &["enum name"]));
+        }
+
+        let enum_name = self.peek(0).unwrap().content.clone();
+        self.type_params = self.parse_type_params(false)?;
+        self.step(1);
+        self.expect_content("{")?;
+        self.step(1);
+
+        let mut variants = Vec::<EnumVariantNode>::new();
+
+        while !self.at_entries_end() {
+            if !self.is_name() {
+                return Err(self.error(ParseErrorKind::InvalidName, &["variant name"]));
+            }
+
+            let variant_start = self.current_token().span;
+            let doc = self.parse_doc();
+            let variant_name = self.peek(0).unwrap().content.clone();
+
+            let mut payload = Vec::new();
+            if self.peek(1).is_some_and(|token| token.is("(")) {
+                // Skip variant name
+                self.step(1);
+                payload = self.parse_list(|parser| parser.parse_type())?;
+            }
+
+            variants.push(EnumVariantNode {
+                name: variant_name,
+                payload,
+                doc,
+                span: self.span_from(variant_start),
+            });
+
+            self.step(1);
+            self.expect_entry_separator()?;
+        }
+
+        Ok(Some(EnumNode {
+            name: enum_name,
+            type_params: mem::take(&mut self.type_params),
+            variants,
+            doc,
+            span: self.span_from(start),
+        }))
+    }
+
+    // <enum>::<variant>(<value>, ...)
+    fn parse_variant_call(&mut self) -> ParseResult<VariantCallNode> {
+        if !self.is_name() || !self.peek(1).is_some_and(|token| token.is("::")) {
+            return Ok(None);
+        }
+        let start = self.current_token().span;
+        let enum_name = self.peek(0).unwrap().content.clone();
+
+        // Skip enum name and "::" symbol
+        self.step(2);
+
+        if !self.is_name() {
+            return Err(self.error(ParseErrorKind::InvalidName, &["variant name"]));
+        }
+
+        let variant = self.peek(0).unwrap().content.clone();
+
+        let mut args = Vec::new();
+        if self.peek(1).is_some_and(|token| token.is("(")) {
+            // Skip variant name
+            self.step(1);
+            args = self.parse_list(|parser| {
+                parser.with_struct_literals(true, Self::parse_expr)?.ok_or_else(|| parser.expected_expr())
+            })?;
+        }
+
+        Ok(Some(VariantCallNode { enum_name, variant, args, type_args: Vec::new(), span: self.span_from(start) }))
+    }
+
+    // match <value> { <pattern> => <body>, ... }, the body of an arm is a block or an expression
+    fn parse_match(&mut self) -> ParseResult<MatchNode> {
+        if !self.equals_content("match") {
+            return Ok(None);
+        }
+        let start = self.current_token().span;
+
+        // Skip "match" keyword
+        self.step(1);
+
+        let value = self.parse_head_expr()?.ok_or_else(|| self.expected_expr())?;
+        self.step(1);
+        self.expect_content("{")?;
+        self.step(1);
+
+        let mut arms = Vec::<MatchArmNode>::new();
+
+        while !self.at_entries_end() {
+            let arm_start = self.current_token().span;
+            let pattern = self.parse_pattern()?;
+
+            // Skip pattern
+            self.step(1);
+            self.expect_content("=>")?;
+
+            // Skip arrow
+            self.step(1);
+
+            let body = match self.parse_block()? {
+                Some(block) => block,
+                None => {
+                    let expr = self.with_struct_literals(true, Self::parse_expr)?
+                        .ok_or_else(|| self.expected_expr())?;
+                    BlockNode { span: expr.span(), nodes: vec![Stmt::Expr(expr)] }
+                }
+            };
+
+            arms.push(MatchArmNode { pattern, body, span: self.span_from(arm_start) });
+
+            self.step(1);
+            self.expect_entry_separator()?;
+        }
+
+        Ok(Some(MatchNode { value, arms, span: self.span_from(start) }))
+    }
+
+    fn parse_pattern(&mut self) -> Result<Pattern, ParseError> {
+        let token = self.current_token();
+
+        if token.is("_") {
+            return Ok(Pattern::Wildcard(token.span));
+        }
+
+        // (<pattern>, <pattern>, ...), a single pattern in parentheses being that pattern
+        if token.is("(") {
+            let mut elements = self.parse_list(|parser| parser.nested(Self::parse_pattern))?;
+
+            return match elements.len() {
+                0 => Err(self.error(ParseErrorKind::UnexpectedToken, &["pattern"])),
+                1 => Ok(elements.remove(0)),
+                _ => Ok(Pattern::Tuple(elements, self.span_from(token.span))),
+            };
+        }
+
+        // Negative numbers are folded by `parse_unary`
+        let negative_number = token.is("-")
+            && self.peek(1).is_some_and(|next| next.token_type == TokenType::Number);
+        if token.is_literal() || token.is("True") || token.is("False") || negative_number {
+            let literal = self.parse_unary()?.ok_or_else(|| self.expected_expr())?;
+            return Ok(Pattern::Literal(literal));
+        }
+
+        if !self.is_name() {
+            return Err(self.error(ParseErrorKind::UnexpectedToken, &["pattern"]));
+        }
+
+        if !self.peek(1).is_some_and(|next| next.is("::")) {
+            return Ok(Pattern::Binding(token.content, token.span));
+        }
+
+        // Skip enum name and "::" symbol
+        self.step(2);
+
+        if !self.is_name() {
+            return Err(self.error(ParseErrorKind::InvalidName, &["variant name"]));
+        }
+
+        let variant = self.peek(0).unwrap().content.clone();
+
+        let mut fields = Vec::new();
+        if self.peek(1).is_some_and(|next| next.is("(")) {
+            // Skip variant name
+            self.step(1);
+            fields = self.parse_list(|parser| parser.nested(Self::parse_pattern))?;
+        }
+
+        Ok(Pattern::Variant { enum_name: token.content, variant, fields, span: self.span_from(token.span) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::lexer::Lexer;
+
+    // Kinds of the errors reported for a program. Nesting up to the limit takes
+    // more stack than a test thread has in a debug build
+    fn errors(source: String) -> Vec<ParseErrorKind> {
+        std::thread::Builder::new().stack_size(64 * 1024 * 1024).spawn(move || {
+            let tokens = Lexer::new().lex(source).unwrap().clone();
+            match Parser::new(tokens).parse() {
+                Ok(_) => Vec::new(),
+                Err(errors) => errors.into_iter().map(|err| err.kind).collect(),
+            }
+        }).unwrap().join().unwrap()
+    }
+
+    fn program(expr: &str) -> String {
+        format!("fcn main() -> Int64 {{\n    return {}\n}}", expr)
+    }
+
+    #[test]
+    fn nested_parentheses() {
+        let nested = |depth| format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+
+        assert_eq!(errors(program(&nested(200))), vec![]);
+        assert_eq!(errors(program(&nested(1000))), vec![ParseErrorKind::TooDeeplyNested]);
+    }
+
+    #[test]
+    fn chained_operations() {
+        let chained = |length| format!("1{}", " + 1".repeat(length));
+
+        assert_eq!(errors(program(&chained(200))), vec![]);
+        assert_eq!(errors(program(&chained(5000))), vec![ParseErrorKind::TooDeeplyNested]);
+        assert_eq!(errors(program(&format!("1{}", " as Int64".repeat(5000)))),
+                   vec![ParseErrorKind::TooDeeplyNested]);
+        assert_eq!(errors(program(&format!("-{}1", "-".repeat(5000)))), vec![ParseErrorKind::TooDeeplyNested]);
+    }
+
+    #[test]
+    fn nested_blocks_are_reported_once() {
+        let nested = |depth| format!("fcn main() -> Int64 {{\n{}{}    return 1\n}}",
+                                     "{\n".repeat(depth), "}\n".repeat(depth));
+
+        assert_eq!(errors(nested(200)), vec![]);
+        assert_eq!(errors(nested(1000)), vec![ParseErrorKind::TooDeeplyNested]);
+    }
+
+    #[test]
+    fn nested_types() {
+        let nested = |depth| format!("fcn f(x :: {}Int64{}) -> Int64 {{\n    return 1\n}}",
+                                     "List<".repeat(depth), ">".repeat(depth));
+
+        assert_eq!(errors(nested(100)), vec![]);
+        assert_eq!(errors(nested(1000)), vec![ParseErrorKind::TooDeeplyNested]);
+    }
+
+    fn parse(source: &str) -> Result<ParseTree, Vec<ParseError>> {
+        let tokens = Lexer::new().lex(source.to_string()).unwrap().clone();
+        Parser::new(tokens).parse()
+    }
+
+    #[test]
+    fn typed_tree() {
+        let tree = parse("
+            fcn f(x :: Int64) -> Bool {
+                if x > 0 {
+                    return True
+                }
+                return False
+            }
+        ").unwrap();
+
+        let function = match &tree.nodes[..] {
+            [Item::Function(function)] => function,
+            nodes => panic!("unexpected items {:?}", nodes),
+        };
+        assert_eq!(function.name, "f");
+        assert_eq!(function.args[0].name, "x");
+        assert_eq!(function.args[0].var_type, Type::Int64);
+        assert_eq!(function.return_type, Type::Bool);
+
+        match &function.block.nodes[..] {
+            [Stmt::If(condition), Stmt::Statement(statement)] => {
+                assert!(matches!(&condition.condition, Expr::BinaryOp(node) if node.operator == BinaryOperator::Greater));
+                assert!(condition.else_branch.is_none());
+                assert_eq!(statement.stat_type, Statement::Return);
+                assert!(matches!(statement.value, Some(Expr::Bool(BoolNode { value: false, .. }))));
+            }
+            nodes => panic!("unexpected statements {:?}", nodes),
+        }
+    }
+
+    // Value of `var x = <expr>`, with every operation in parentheses
+    fn grouped(expr: &str) -> String {
+        fn group(expr: &Expr) -> String {
+            match expr {
+                Expr::Number(NumberNode { value: Number::Integer { negative, magnitude }, .. }) =>
+                    format!("{}{}", if *negative { "-" } else { "" }, magnitude),
+                Expr::VariableCall(node) => node.name.clone(),
+                Expr::BinaryOp(node) => format!("({} {} {})", group(&node.left), node.operator, group(&node.right)),
+                Expr::UnaryOp(node) => format!("({}{})", node.operator, group(&node.operand)),
+                Expr::Cast(node) => format!("({} as {})", group(&node.expr), node.target),
+                expr => panic!("unexpected expression {:?}", expr),
+            }
+        }
+
+        match &parse(&format!("var x = {}", expr)).unwrap().nodes[..] {
+            [Item::Variable(node)] => group(&node.value),
+            nodes => panic!("unexpected items {:?}", nodes),
+        }
+    }
+
+    #[test]
+    fn operator_precedence() {
+        assert_eq!(grouped("a + b * c - d"), "((a + (b * c)) - d)");
+        assert_eq!(grouped("a - b - c"), "((a - b) - c)");
+        assert_eq!(grouped("a or b and c == d"), "(a || (b && (c == d)))");
+        assert_eq!(grouped("a | b ^ c & d"), "(a | (b ^ (c & d)))");
+        assert_eq!(grouped("a < b << c + d"), "(a < (b << (c + d)))");
+        assert_eq!(grouped("-a * ~b"), "((-a) * (~b))");
+        assert_eq!(grouped("-a as UInt8 + b"), "(((-a) as UInt8) + b)");
+        assert_eq!(grouped("(a + b) * c"), "((a + b) * c)");
+        // Negative literals are folded
+        assert_eq!(grouped("- -1"), "1");
+    }
+
+    #[test]
+    fn missing_operand() {
+        let kinds = |source: &str| parse(source).unwrap_err().into_iter().map(|err| err.kind).collect::<Vec<_>>();
+
+        assert_eq!(kinds("var x = 1 +"), [ParseErrorKind::MissingExpression]);
+        assert_eq!(kinds("var x = -"), [ParseErrorKind::MissingExpression]);
+    }
+
+    #[test]
+    fn several_items() {
+        let tree = parse("
+            struct P {
+                x :: Int64
+            }
+
+            fcn f() -> Int64 {
+                return 1
+            }
+            var g = f()
+            enum E { A, B }
+        ").unwrap();
+
+        assert!(matches!(&tree.nodes[..], [Item::Struct(_), Item::Function(_), Item::Variable(_), Item::Enum(_)]));
+    }
+
+    #[test]
+    fn items_on_one_line() {
+        let errors = parse("var a = 1 var b = 2\nvar c = 3 +").unwrap_err();
+
+        let kinds = errors.iter().map(|err| (err.kind.clone(), err.expectation())).collect::<Vec<_>>();
+        assert_eq!(kinds, [
+            (ParseErrorKind::UnexpectedToken, "expected new line, got 'var'".to_string()),
+            (ParseErrorKind::MissingExpression, "expected expression, got end of input".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn statements_are_not_items() {
+        let errors = parse("return 1").unwrap_err();
+        assert_eq!(errors[0].kind, ParseErrorKind::UnexpectedToken);
+        assert_eq!(errors[0].expected.len(), 8);
+    }
+
+    #[test]
+    fn errors_are_collected() {
+        let errors = parse("var = 1\nfcn f() -> Int64 {\n    var y = )\n    return 1 +\n}\nvar z = 2").unwrap_err();
+
+        let found = errors.iter()
+            .map(|err| (err.kind.clone(), err.token.content.as_str(), err.span, err.expectation()))
+            .collect::<Vec<_>>();
+        assert_eq!(found, [
+            (ParseErrorKind::InvalidName, "=", Span::new(4, 5), "expected variable name, got '='".to_string()),
+            (ParseErrorKind::MissingExpression, ")", Span::new(39, 40), "expected expression, got ')'".to_string()),
+            (ParseErrorKind::MissingExpression, "\n", Span::new(55, 56), "expected expression, got new line".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn doc_comments() {
+        let tree = parse("
+            /// A point
+            struct P {
+                /// First
+                /// coordinate
+                x :: Int64
+            }
+            enum E {
+                /// Nothing
+                A
+            }
+            trait Tr {
+                fcn get(self) -> Int64
+            }
+            /// Gives x
+            impl Tr for P {
+                fcn get(self) -> Int64 {
+                    return self.x
+                }
+            }
+        ").unwrap();
+
+        match &tree.nodes[..] {
+            [Item::Struct(point), Item::Enum(node), Item::Trait(_), Item::Impl(impl_node)] => {
+                assert_eq!(point.doc.as_deref(), Some("A point"));
+                assert_eq!(point.fields[0].doc.as_deref(), Some("First\ncoordinate"));
+                assert_eq!(node.variants[0].doc.as_deref(), Some("Nothing"));
+                assert_eq!(impl_node.doc.as_deref(), Some("Gives x"));
+            }
+            nodes => panic!("unexpected items {:?}", nodes),
+        }
+    }
+
+    #[test]
+    fn stray_doc_comments() {
+        let errors = parse("
+            fcn main() -> Int64 {
+                /// Documents the local variable
+                let x = 1
+                /// Nothing to document
+                /// on both lines
+                return x
+                /// Nor here
+            }
+            /// Nor at the end
+        ").unwrap_err();
+
+        let lines = errors.iter()
+            .map(|err| (err.kind.clone(), err.token.content.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [
+            (ParseErrorKind::StrayDocComment, "/// Nothing to document".to_string()),
+            (ParseErrorKind::StrayDocComment, "/// Nor here".to_string()),
+            (ParseErrorKind::StrayDocComment, "/// Nor at the end".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn number_too_large() {
+        let kinds = |source: &str| parse(source).unwrap_err().into_iter().map(|err| err.kind).collect::<Vec<_>>();
+        let program = |expr: &str| format!("fcn main() -> UInt128 {{\n    return {}\n}}", expr);
+
+        assert!(parse(&program("340282366920938463463374607431768211455")).is_ok());
+        assert_eq!(kinds(&program("340282366920938463463374607431768211456")),
+                   [ParseErrorKind::NumberTooLarge(128)]);
+        assert_eq!(kinds(&program(&format!("0x1{}", "0".repeat(32)))), [ParseErrorKind::NumberTooLarge(128)]);
+        assert_eq!(kinds("var a :: [Int64; 99999999999999999999999] = [1; 2]"),
+                   [ParseErrorKind::NumberTooLarge(usize::BITS)]);
     }
 }
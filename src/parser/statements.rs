@@ -1,4 +1,6 @@
-use crate::lexer::token::Token;
+use std::fmt;
+
+use crate::lexer::token::{Token, TokenType};
 
 #[derive(Debug, Eq, PartialEq)]
 pub enum Statement {
@@ -9,7 +11,11 @@ pub enum Statement {
 
 impl Statement {
     pub fn from_token(token: &Token) -> Option<Self> {
-        return match token.content.as_str() {
+        if token.token_type != TokenType::Keyword {
+            return None;
+        }
+
+        match token.content.as_str() {
             "return" => Some(Self::Return),
             "break" => Some(Self::Break),
             "continue" => Some(Self::Continue),
@@ -17,3 +23,13 @@ impl Statement {
         }
     }
 }
+
+impl fmt::Display for Statement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Return => write!(f, "return"),
+            Self::Break => write!(f, "break"),
+            Self::Continue => write!(f, "continue"),
+        }
+    }
+}
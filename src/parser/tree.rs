@@ -1,12 +1,12 @@
-use std::fmt::Debug;
-
+use crate::diagnostics::span::Span;
 use crate::parser::types::Type;
 use crate::parser::statements::Statement;
+use crate::parser::variables::VariableKind;
+use crate::parser::operators::{AssignOperator, BinaryOperator, UnaryOperator};
 
-pub type Node = Box<dyn ParseNode>;
-
+#[derive(Debug, Default)]
 pub struct ParseTree {
-    nodes: Vec<Box<dyn ParseNode>>,
+    pub nodes: Vec<Item>,
 }
 
 impl ParseTree {
@@ -17,84 +17,517 @@ impl ParseTree {
     }
 }
 
-pub trait ParseNode: Debug {}
+// Top-level items of a compilation unit
+#[derive(Debug)]
+pub enum Item {
+    Function(FunctionNode),
+    Variable(VariableNode),
+    Struct(StructNode),
+    Enum(EnumNode),
+    Trait(TraitNode),
+    Impl(ImplNode),
+}
+
+impl Item {
+    pub fn span(&self) -> Span {
+        match self {
+            Item::Function(node) => node.span,
+            Item::Variable(node) => node.span,
+            Item::Struct(node) => node.span,
+            Item::Enum(node) => node.span,
+            Item::Trait(node) => node.span,
+            Item::Impl(node) => node.span,
+        }
+    }
+}
+
+// A single entry of the interactive interpreter
+#[derive(Debug)]
+pub enum Input {
+    Item(Item),
+    Stmt(Stmt),
+}
+
+// Nodes that can appear inside a block
+#[derive(Debug)]
+pub enum Stmt {
+    Variable(VariableNode),
+    Destructure(DestructureNode),
+    Block(BlockNode),
+    If(IfNode),
+    Loop(LoopNode),
+    Match(MatchNode),
+    Assign(AssignNode),
+    Statement(StatementNode),
+    Expr(Expr),
+}
+
+impl Stmt {
+    pub fn span(&self) -> Span {
+        match self {
+            Stmt::Variable(node) => node.span,
+            Stmt::Destructure(node) => node.span,
+            Stmt::Block(node) => node.span,
+            Stmt::If(node) => node.span,
+            Stmt::Loop(node) => node.span,
+            Stmt::Match(node) => node.span,
+            Stmt::Assign(node) => node.span,
+            Stmt::Statement(node) => node.span,
+            Stmt::Expr(expr) => expr.span(),
+        }
+    }
+
+    // Whether the statement gives its value to a block ending with it: an
+    // expression, or an 'if' or a 'match' which was parsed as a statement
+    pub fn is_tail_value(&self) -> bool {
+        matches!(self, Stmt::Expr(_) | Stmt::If(_) | Stmt::Match(_))
+    }
+}
+
+// Nodes that evaluate to a value
+#[derive(Debug)]
+pub enum Expr {
+    Bool(BoolNode),
+    Number(NumberNode),
+    String(StringNode),
+    Char(CharNode),
+    FunctionCall(FunctionCallNode),
+    VariableCall(VariableCallNode),
+    BinaryOp(BinaryOpNode),
+    UnaryOp(UnaryOpNode),
+    If(Box<IfNode>),
+    Cast(CastNode),
+    StructLiteral(StructLiteralNode),
+    Field(FieldNode),
+    VariantCall(VariantCallNode),
+    Match(Box<MatchNode>),
+    Array(ArrayNode),
+    Index(IndexNode),
+    MethodCall(MethodCallNode),
+    Tuple(TupleNode),
+}
+
+impl Expr {
+    pub fn span(&self) -> Span {
+        match self {
+            Expr::Bool(node) => node.span,
+            Expr::Number(node) => node.span,
+            Expr::String(node) => node.span,
+            Expr::Char(node) => node.span,
+            Expr::FunctionCall(node) => node.span,
+            Expr::VariableCall(node) => node.span,
+            Expr::BinaryOp(node) => node.span,
+            Expr::UnaryOp(node) => node.span,
+            Expr::If(node) => node.span,
+            Expr::Cast(node) => node.span,
+            Expr::StructLiteral(node) => node.span,
+            Expr::Field(node) => node.span,
+            Expr::VariantCall(node) => node.span,
+            Expr::Match(node) => node.span,
+            Expr::Array(node) => node.span,
+            Expr::Index(node) => node.span,
+            Expr::MethodCall(node) => node.span,
+            Expr::Tuple(node) => node.span,
+        }
+    }
+
+    // Whether the expression is only made of number literals, in which case
+    // its type is given by the context it appears in
+    pub fn is_untyped(&self) -> bool {
+        match self {
+            Expr::Number(_) => true,
+            Expr::UnaryOp(node) => node.operand.is_untyped(),
+            Expr::BinaryOp(node) => !node.operator.is_comparison() && !node.operator.is_logical()
+                && node.left.is_untyped() && node.right.is_untyped(),
+            _ => false,
+        }
+    }
+}
 
 #[derive(Debug)]
 pub struct VariableNode {
-    pub var_type: Type,
+    pub kind: VariableKind,
+    // None until inferred by the checker if the declaration gives no type
+    pub var_type: Option<Type>,
     pub name: String,
-    pub value: Box<dyn ParseNode>,
+    pub value: Expr,
+    // Content of the doc comments (///) right above the declaration
+    pub doc: Option<String>,
+    pub span: Span,
 }
 
-impl ParseNode for VariableNode {}
+// var (<pattern>, ...) = <value>, only allowed inside blocks
+#[derive(Debug)]
+pub struct DestructureNode {
+    pub kind: VariableKind,
+    // Must match every value of the type, which is enforced by the checker
+    pub pattern: Pattern,
+    pub var_type: Option<Type>,
+    pub value: Expr,
+    pub span: Span,
+}
 
 #[derive(Debug)]
 pub struct BoolNode {
     pub value: bool,
+    pub span: Span,
 }
 
-impl ParseNode for BoolNode {}
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Number {
+    // Sign and absolute value, so that both the lowest Int128 and the
+    // highest UInt128 can be written
+    Integer { negative: bool, magnitude: u128 },
+    Float(f64),
+}
 
-// TODO: support floats and negative numbers
 #[derive(Debug)]
 pub struct NumberNode {
-    pub int_value: usize,
+    pub value: Number,
+    // Type the literal is given by its context, set by the checker
+    pub number_type: Option<Type>,
+    pub span: Span,
 }
 
-impl ParseNode for NumberNode {}
-
 #[derive(Debug)]
 pub struct StringNode {
     pub value: String,
+    pub span: Span,
 }
 
-impl ParseNode for StringNode {}
+#[derive(Debug)]
+pub struct CharNode {
+    pub value: char,
+    pub span: Span,
+}
 
 #[derive(Debug)]
 pub struct FunctionCallNode {
     pub name: String,
-    pub args: Vec<Node>,
+    pub args: Vec<Expr>,
+    // Types given to the type parameters of a generic function, set by the checker
+    pub type_args: Vec<Type>,
+    pub span: Span,
 }
 
-impl ParseNode for FunctionCallNode {}
-
 #[derive(Debug)]
 pub struct VariableCallNode {
     pub name: String,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct BinaryOpNode {
+    pub operator: BinaryOperator,
+    pub left: Box<Expr>,
+    pub right: Box<Expr>,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct UnaryOpNode {
+    pub operator: UnaryOperator,
+    pub operand: Box<Expr>,
+    pub span: Span,
+}
+
+// <expr> as <target>
+#[derive(Debug)]
+pub struct CastNode {
+    pub expr: Box<Expr>,
+    pub target: Type,
+    pub span: Span,
+}
+
+// <name> { <field>: <value>, ... }
+#[derive(Debug)]
+pub struct StructLiteralNode {
+    pub name: String,
+    pub fields: Vec<FieldValueNode>,
+    // Types given to the type parameters of a generic struct, set by the checker
+    pub type_args: Vec<Type>,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct FieldValueNode {
+    pub name: String,
+    pub value: Expr,
+    pub span: Span,
+}
+
+// <expr>.<field>
+#[derive(Debug)]
+pub struct FieldNode {
+    pub expr: Box<Expr>,
+    pub field: String,
+    pub span: Span,
+}
+
+// [<value>, ...] or [<value>; <length>]
+#[derive(Debug)]
+pub struct ArrayNode {
+    pub elements: ArrayElements,
+    // Array or list type of the literal, None until set by the checker
+    pub array_type: Option<Type>,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub enum ArrayElements {
+    Values(Vec<Expr>),
+    // The value is evaluated once and copied `length` times
+    Repeat { value: Box<Expr>, length: usize },
+}
+
+// (<value>, <value>, ...), with at least two values
+#[derive(Debug)]
+pub struct TupleNode {
+    pub elements: Vec<Expr>,
+    pub span: Span,
+}
+
+// <expr>[<index>]
+#[derive(Debug)]
+pub struct IndexNode {
+    pub expr: Box<Expr>,
+    pub index: Box<Expr>,
+    pub span: Span,
 }
 
-impl ParseNode for VariableCallNode {}
+// <expr>.<method>(<value>, ...)
+#[derive(Debug)]
+pub struct MethodCallNode {
+    pub receiver: Box<Expr>,
+    pub method: String,
+    pub args: Vec<Expr>,
+    // Trait declaring the method, set by the checker. None for built-in methods
+    pub trait_name: Option<String>,
+    pub span: Span,
+}
+
+// <enum>::<variant>(<value>, ...), without parentheses if the variant has no payload
+#[derive(Debug)]
+pub struct VariantCallNode {
+    pub enum_name: String,
+    pub variant: String,
+    pub args: Vec<Expr>,
+    // Types given to the type parameters of a generic enum, set by the checker
+    pub type_args: Vec<Type>,
+    pub span: Span,
+}
 
 #[derive(Debug)]
 pub struct BlockNode {
-    pub nodes: Vec<Node>,
+    pub nodes: Vec<Stmt>,
+    pub span: Span,
+}
+
+// Used as a statement, or as an expression whose value is the
+// trailing expression of the branch taken
+#[derive(Debug)]
+pub struct IfNode {
+    pub condition: Expr,
+    pub then_block: BlockNode,
+    pub else_branch: Option<ElseBranch>,
+    pub span: Span,
 }
 
-impl ParseNode for BlockNode {}
+#[derive(Debug)]
+pub enum ElseBranch {
+    If(Box<IfNode>),
+    Block(BlockNode),
+}
+
+impl ElseBranch {
+    pub fn span(&self) -> Span {
+        match self {
+            ElseBranch::If(node) => node.span,
+            ElseBranch::Block(node) => node.span,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct LoopNode {
+    pub kind: LoopKind,
+    // Name given with 'name: before the loop, without the quote
+    pub label: Option<String>,
+    pub block: BlockNode,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub enum LoopKind {
+    // loop { ... }
+    Infinite,
+    // while <condition> { ... }
+    While(Expr),
+    // for <var_name> in <start>..<end> { ... }, `end` excluded
+    For { var_name: String, start: Expr, end: Expr },
+}
+
+// Used as a statement, or as an expression whose value is the
+// trailing expression of the arm taken
+#[derive(Debug)]
+pub struct MatchNode {
+    pub value: Expr,
+    pub arms: Vec<MatchArmNode>,
+    pub span: Span,
+}
+
+// <pattern> => <body>, an expression body is stored as a block holding only it
+#[derive(Debug)]
+pub struct MatchArmNode {
+    pub pattern: Pattern,
+    pub body: BlockNode,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub enum Pattern {
+    // _
+    Wildcard(Span),
+    // Name bound to the matched value
+    Binding(String, Span),
+    // Bool, number, string or char literal
+    Literal(Expr),
+    // <enum>::<variant>(<pattern>, ...)
+    Variant { enum_name: String, variant: String, fields: Vec<Pattern>, span: Span },
+    // (<pattern>, <pattern>, ...)
+    Tuple(Vec<Pattern>, Span),
+}
+
+impl Pattern {
+    pub fn span(&self) -> Span {
+        match self {
+            Pattern::Wildcard(span) => *span,
+            Pattern::Binding(_, span) => *span,
+            Pattern::Literal(expr) => expr.span(),
+            Pattern::Variant { span, .. } => *span,
+            Pattern::Tuple(_, span) => *span,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AssignNode {
+    // Only variables and their fields can be assigned to, which is enforced by the checker
+    pub target: Expr,
+    pub operator: AssignOperator,
+    pub value: Expr,
+    pub span: Span,
+}
 
 #[derive(Debug)]
 pub struct FunctionNode {
     pub name: String,
+    pub type_params: Vec<String>,
+    pub bounds: Vec<BoundNode>,
     pub args: Vec<FunctionArgNode>,
     pub return_type: Type,
-    pub block: Box<dyn ParseNode>,
+    pub block: BlockNode,
+    // Content of the doc comments (///) right above the declaration
+    pub doc: Option<String>,
+    pub span: Span,
 }
 
-impl ParseNode for FunctionNode {}
-
 #[derive(Debug)]
 pub struct FunctionArgNode {
     pub var_type: Type,
     pub name: String,
+    pub span: Span,
+}
+
+// <param>: <trait> among the type parameters of a function or an impl,
+// `T: A + B` giving a bound per trait
+#[derive(Debug, Clone)]
+pub struct BoundNode {
+    pub param: String,
+    pub trait_name: String,
+    pub span: Span,
+}
+
+// trait <name> { fcn <method>(self, <arg> :: <type>, ...) -> <type> ... }
+#[derive(Debug)]
+pub struct TraitNode {
+    pub name: String,
+    pub methods: Vec<MethodDeclNode>,
+    // Content of the doc comments (///) right above the declaration
+    pub doc: Option<String>,
+    pub span: Span,
+}
+
+// Method of a trait, declared without a body
+#[derive(Debug)]
+pub struct MethodDeclNode {
+    pub name: String,
+    pub args: Vec<FunctionArgNode>,
+    pub return_type: Type,
+    pub doc: Option<String>,
+    pub span: Span,
+}
+
+// impl<<param>, ...> <trait> for <type> { <method> ... }
+#[derive(Debug)]
+pub struct ImplNode {
+    pub type_params: Vec<String>,
+    pub bounds: Vec<BoundNode>,
+    pub trait_name: String,
+    pub target: Type,
+    // `Self` is replaced by the target in their signatures, and they take
+    // the type parameters and bounds of the impl
+    pub methods: Vec<FunctionNode>,
+    // Content of the doc comments (///) right above the declaration
+    pub doc: Option<String>,
+    pub span: Span,
+}
+
+// struct <name> { <field> :: <type>, ... }
+#[derive(Debug)]
+pub struct StructNode {
+    pub name: String,
+    pub type_params: Vec<String>,
+    pub fields: Vec<StructFieldNode>,
+    // Content of the doc comments (///) right above the declaration
+    pub doc: Option<String>,
+    pub span: Span,
 }
 
-impl ParseNode for FunctionArgNode {}
+#[derive(Debug)]
+pub struct StructFieldNode {
+    pub field_type: Type,
+    pub name: String,
+    pub doc: Option<String>,
+    pub span: Span,
+}
+
+// enum <name> { <variant>(<type>, ...), ... }
+#[derive(Debug)]
+pub struct EnumNode {
+    pub name: String,
+    pub type_params: Vec<String>,
+    pub variants: Vec<EnumVariantNode>,
+    // Content of the doc comments (///) right above the declaration
+    pub doc: Option<String>,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct EnumVariantNode {
+    pub name: String,
+    // Types of the values carried by the variant, empty if it has none
+    pub payload: Vec<Type>,
+    pub doc: Option<String>,
+    pub span: Span,
+}
 
 #[derive(Debug)]
 pub struct StatementNode {
     pub stat_type: Statement,
     // Only if the statement is 'return'
-    pub value: Option<Box<dyn ParseNode>>,
+    pub value: Option<Expr>,
+    // Loop targeted by 'break' or 'continue', the innermost one if None
+    pub label: Option<String>,
+    pub span: Span,
 }
-
-impl ParseNode for StatementNode {}
@@ -1,25 +1,54 @@
-use crate::lexer::token::Token;
+use std::collections::HashMap;
+use std::fmt;
 
-#[derive(Debug)]
+use crate::lexer::token::{Token, TokenType};
+
+#[derive(Debug, Eq, PartialEq, PartialOrd, Clone)]
 pub enum Type {
     Int8,
     Int16,
     Int32,
     Int64,
+    Int128,
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
+    UInt128,
     Float32,
     Float64,
     Bool,
     Char,
     String,
+    // Struct or enum with its type arguments, whose declaration is looked up by the checker
+    Named(String, Vec<Type>),
+    // Type parameter of the generic declaration it appears in
+    Param(String),
+    // [<type>; <length>]
+    Array(Box<Type>, usize),
+    // List<<type>>, which can grow
+    List(Box<Type>),
+    // (<type>, <type>, ...)
+    Tuple(Vec<Type>),
 }
 
 impl Type {
     pub fn from_token(token: &Token) -> Option<Self> {
-        return match token.content.as_str() {
+        if token.token_type != TokenType::Keyword {
+            return None;
+        }
+
+        match token.content.as_str() {
             "Int8" => Some(Self::Int8),
             "Int16" => Some(Self::Int16),
             "Int32" => Some(Self::Int32),
             "Int64" => Some(Self::Int64),
+            "Int128" => Some(Self::Int128),
+            "UInt8" => Some(Self::UInt8),
+            "UInt16" => Some(Self::UInt16),
+            "UInt32" => Some(Self::UInt32),
+            "UInt64" => Some(Self::UInt64),
+            "UInt128" => Some(Self::UInt128),
             "Float32" => Some(Self::Float32),
             "Float64" => Some(Self::Float64),
             "Bool" => Some(Self::Bool),
@@ -28,4 +57,129 @@ impl Type {
             _ => None
         }
     }
+
+    pub fn is_integer(&self) -> bool {
+        self.bits().is_some()
+    }
+
+    pub fn is_unsigned(&self) -> bool {
+        matches!(self, Self::UInt8 | Self::UInt16 | Self::UInt32 | Self::UInt64 | Self::UInt128)
+    }
+
+    // Number of bits of an integer type
+    pub fn bits(&self) -> Option<u32> {
+        match self {
+            Self::Int8 | Self::UInt8 => Some(8),
+            Self::Int16 | Self::UInt16 => Some(16),
+            Self::Int32 | Self::UInt32 => Some(32),
+            Self::Int64 | Self::UInt64 => Some(64),
+            Self::Int128 | Self::UInt128 => Some(128),
+            _ => None,
+        }
+    }
+
+    pub fn is_float(&self) -> bool {
+        matches!(self, Self::Float32 | Self::Float64)
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        self.is_integer() || self.is_float()
+    }
+
+    // Whether a value of this type can be converted to `target` with 'as':
+    // numbers to numbers, bools and chars to integers, and UInt8 to chars
+    pub fn can_cast_to(&self, target: &Type) -> bool {
+        self == target
+            || (self.is_numeric() && target.is_numeric())
+            || (matches!(self, Self::Bool | Self::Char) && target.is_integer())
+            || (*self == Self::UInt8 && *target == Self::Char)
+    }
+
+    // Type of the elements of an array or a list
+    pub fn element_type(&self) -> Option<&Type> {
+        match self {
+            Self::Array(element_type, _) | Self::List(element_type) => Some(element_type),
+            _ => None,
+        }
+    }
+
+    // Replaces the type parameters bound in `bindings` by their type
+    pub fn substitute(&self, bindings: &HashMap<String, Type>) -> Type {
+        let substitute_all = |types: &[Type]| types.iter().map(|t| t.substitute(bindings)).collect();
+
+        match self {
+            Self::Param(name) => bindings.get(name).cloned().unwrap_or_else(|| self.clone()),
+            Self::Named(name, args) => Self::Named(name.clone(), substitute_all(args)),
+            Self::Array(element_type, length) => Self::Array(Box::new(element_type.substitute(bindings)), *length),
+            Self::List(element_type) => Self::List(Box::new(element_type.substitute(bindings))),
+            Self::Tuple(types) => Self::Tuple(substitute_all(types)),
+            _ => self.clone(),
+        }
+    }
+
+    // Type parameters the type refers to, in order of appearance
+    pub fn params(&self) -> Vec<&str> {
+        match self {
+            Self::Param(name) => vec![name.as_str()],
+            Self::Named(_, types) | Self::Tuple(types) => types.iter().flat_map(Type::params).collect(),
+            Self::Array(element_type, _) | Self::List(element_type) => element_type.params(),
+            _ => Vec::new(),
+        }
+    }
+
+    // Lowest and highest values of an integer type
+    pub fn integer_range(&self) -> Option<(i128, u128)> {
+        let shift = 128 - self.bits()?;
+
+        if self.is_unsigned() {
+            Some((0, u128::MAX >> shift))
+        } else {
+            Some((i128::MIN >> shift, (i128::MAX >> shift) as u128))
+        }
+    }
+
+    // Whether the integer of sign `negative` and absolute value `magnitude`
+    // is a value of the type
+    pub fn contains(&self, negative: bool, magnitude: u128) -> bool {
+        match self.integer_range() {
+            Some((min, _)) if negative && magnitude != 0 => magnitude <= min.unsigned_abs(),
+            Some((_, max)) => magnitude <= max,
+            None => false,
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::Array(element_type, length) => return write!(f, "[{}; {}]", element_type, length),
+            Self::List(element_type) => return write!(f, "List<{}>", element_type),
+            Self::Named(name, args) if !args.is_empty() => {
+                let args = args.iter().map(Type::to_string).collect::<Vec<_>>();
+                return write!(f, "{}<{}>", name, args.join(", "));
+            }
+            Self::Param(name) => return write!(f, "{}", name),
+            Self::Tuple(types) => {
+                let types = types.iter().map(Type::to_string).collect::<Vec<_>>();
+                return write!(f, "({})", types.join(", "));
+            }
+            Self::Int8 => "Int8",
+            Self::Int16 => "Int16",
+            Self::Int32 => "Int32",
+            Self::Int64 => "Int64",
+            Self::Int128 => "Int128",
+            Self::UInt8 => "UInt8",
+            Self::UInt16 => "UInt16",
+            Self::UInt32 => "UInt32",
+            Self::UInt64 => "UInt64",
+            Self::UInt128 => "UInt128",
+            Self::Float32 => "Float32",
+            Self::Float64 => "Float64",
+            Self::Bool => "Bool",
+            Self::Char => "Char",
+            Self::String => "String",
+            Self::Named(name, _) => name.as_str(),
+        };
+        write!(f, "{}", name)
+    }
 }
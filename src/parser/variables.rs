@@ -0,0 +1,43 @@
+use std::fmt;
+
+use crate::lexer::token::{Token, TokenType};
+
+// Keyword a variable is declared with
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum VariableKind {
+    // Mutable
+    Var,
+    // Immutable
+    Let,
+    // Immutable, initialized with a constant expression at the top level
+    Const,
+}
+
+impl VariableKind {
+    pub fn from_token(token: &Token) -> Option<Self> {
+        if token.token_type != TokenType::Keyword {
+            return None;
+        }
+
+        match token.content.as_str() {
+            "var" => Some(Self::Var),
+            "let" => Some(Self::Let),
+            "const" => Some(Self::Const),
+            _ => None,
+        }
+    }
+
+    pub fn is_mutable(&self) -> bool {
+        *self == Self::Var
+    }
+}
+
+impl fmt::Display for VariableKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Var => write!(f, "var"),
+            Self::Let => write!(f, "let"),
+            Self::Const => write!(f, "const"),
+        }
+    }
+}